@@ -1,4 +1,69 @@
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct Enemy;
+
+/// Archétype d'un fichier ennemi, chacun avec ses points de vie, sa vitesse et sa valeur
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EnemyKind {
+    Fixme,
+    /// avance en zig-zag
+    FlakyTest,
+    /// se coupe en deux `ConflictHalf` quand il est touché
+    MergeConflict,
+    ConflictHalf,
+    /// encaisse plusieurs tirs
+    LegacyCode,
+    /// tombe très vite
+    NullPointer
+}
+
+impl EnemyKind {
+    pub fn hit_points(&self) -> u32 {
+        match self {
+            EnemyKind::LegacyCode => 3,
+            _ => 1
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            EnemyKind::Fixme | EnemyKind::ConflictHalf => 1,
+            EnemyKind::FlakyTest | EnemyKind::MergeConflict => 2,
+            EnemyKind::NullPointer => 3,
+            EnemyKind::LegacyCode => 5
+        }
+    }
+
+    /// multiplie la vitesse de chute de base
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            EnemyKind::Fixme => 1.,
+            EnemyKind::FlakyTest => 0.8,
+            EnemyKind::MergeConflict => 0.7,
+            EnemyKind::ConflictHalf => 1.2,
+            EnemyKind::LegacyCode => 0.5,
+            EnemyKind::NullPointer => 2.
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            EnemyKind::ConflictHalf => 0.6,
+            _ => 1.
+        }
+    }
+}
+
+/// Mouvement de balancier horizontal des flaky tests
+#[derive(Component)]
+pub struct ZigZag {
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub elapsed: f32
+}
+
+/// Commentaire porté par l'ennemi en mode scan, indice dans `ScanComments::all`
+#[derive(Component)]
+pub struct SourceLabel(pub usize);
@@ -0,0 +1,53 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Health { current: max, max }
+    }
+}
+
+/// Dégâts infligés par un projectile à l'impact
+#[derive(Component)]
+pub struct Damage(pub u32);
+
+/// Teinte rouge brève après un impact, pour lire les ennemis qui encaissent plusieurs tirs
+#[derive(Component)]
+pub struct HitFlash {
+    pub duration: f32,
+    pub current_time: f32
+}
+
+impl Default for HitFlash {
+    fn default() -> Self {
+        // inactif tant que rien n'a touché l'entité
+        HitFlash { duration: 0.1, current_time: 0.1 }
+    }
+}
+
+impl HitFlash {
+    pub fn trigger(&mut self) {
+        self.current_time = 0.;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.current_time < self.duration
+    }
+}
+
+/// Pas restants pendant lesquels l'entité ne prend plus de dégats après un choc
+#[derive(Component, Default)]
+pub struct Invulnerability {
+    pub ticks: u32
+}
+
+impl Invulnerability {
+    pub fn is_active(&self) -> bool {
+        self.ticks > 0
+    }
+}
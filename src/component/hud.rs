@@ -1,4 +1,27 @@
 use bevy::prelude::Component;
 
 #[derive(Component)]
-pub struct TextTimer;
\ No newline at end of file
+pub struct TextTimer;
+
+#[derive(Component)]
+pub struct TextLives;
+
+// les textes et la jauge propres à un joueur portent son indice
+
+#[derive(Component)]
+pub struct TextWeapon(pub usize);
+
+/// Jauge sous l'arme : délai avant le prochain tir, ou charge en cours
+#[derive(Component)]
+pub struct WeaponGauge(pub usize);
+
+/// Bonus actifs et leur durée restante
+#[derive(Component)]
+pub struct TextPowerUps;
+
+#[derive(Component)]
+pub struct TextIntegrity(pub usize);
+
+/// Points d'un joueur, seulement en coop
+#[derive(Component)]
+pub struct TextPlayerScore(pub usize);
@@ -1,4 +1,17 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Entity};
 
 #[derive(Component)]
 pub struct Laser;
+
+/// Le fichier traverse les ennemis, chacun n'est touché qu'une fois
+#[derive(Component, Default)]
+pub struct Piercing {
+    pub hit: Vec<Entity>
+}
+
+/// Le fichier s'oriente vers l'ennemi le plus proche
+#[derive(Component)]
+pub struct Homing {
+    /// part de la direction corrigée à chaque pas, entre 0 et 1
+    pub turn_rate: f32
+}
@@ -1,4 +1,35 @@
 use bevy::prelude::Component;
 
+use crate::resources::keymap::Action;
+
+#[derive(Component)]
+pub struct TextMenu;
+
+#[derive(Component)]
+pub struct TextNameEntry;
+
+#[derive(Component)]
+pub struct TextControls;
+
+#[derive(Component)]
+pub struct TextPlayers;
+
 #[derive(Component)]
-pub struct TextMenu;
\ No newline at end of file
+pub struct TextReplayList;
+
+/// Texte qui affiche des touches : chaque "{}" du modèle est remplacé par la touche ou le bouton
+/// de l'action correspondante. La dernière section du texte est réécrite quand le joueur change d'appareil
+#[derive(Component)]
+pub struct ActionPrompt {
+    pub template: &'static str,
+    pub actions: Vec<Action>
+}
+
+impl ActionPrompt {
+    pub fn new(template: &'static str, actions: &[Action]) -> Self {
+        ActionPrompt {
+            template,
+            actions: actions.to_vec()
+        }
+    }
+}
@@ -8,4 +8,6 @@ pub mod menu;
 pub mod push_file;
 pub mod hud;
 pub mod state;
-pub mod temporary;
\ No newline at end of file
+pub mod temporary;
+pub mod health;
+pub mod weapon;
\ No newline at end of file
@@ -1,6 +1,20 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Vec3};
 
 #[derive(Component)]
 pub struct Movable {
     pub auto_despawn: bool,
-}
\ No newline at end of file
+}
+
+/// Position de simulation aux deux derniers pas fixes,
+/// le Transform affiché est interpolé entre les deux
+#[derive(Component)]
+pub struct SimPosition {
+    pub previous: Vec3,
+    pub current: Vec3
+}
+
+impl SimPosition {
+    pub fn at(translation: Vec3) -> Self {
+        SimPosition { previous: translation, current: translation }
+    }
+}
@@ -1,10 +1,41 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Vec2};
 
+use crate::component::weapon::WeaponKind;
+
+/// Pc d'un joueur, `index` 0 pour le premier et 1 pour le second en coop
 #[derive(Component)]
-pub struct Player;
+pub struct Player {
+    pub index: usize
+}
+
+/// Commandes du joueur échantillonnées à chaque frame et consommées par la simulation à pas fixe
+#[derive(Component, Default)]
+pub struct PlayerInput {
+    pub x: f32,
+    pub y: f32,
+    // reste à true jusqu'au prochain pas fixe, pour ne perdre aucun tir
+    pub fire: bool,
+    // touche de tir maintenue, pour charger un tir
+    pub hold: bool,
+    // gardé comme le tir jusqu'au prochain pas
+    pub dash: bool,
+    // changement d'arme demandé, gardé de meme jusqu'au prochain pas
+    pub weapon: Option<WeaponKind>
+}
+
+/// Accélération brève du pc dans la direction des flèches
+#[derive(Component, Default)]
+pub struct Dash {
+    /// pas restants du dash en cours
+    pub ticks: u32,
+    /// pas restants avant de pouvoir dasher à nouveau
+    pub cooldown: u32,
+    pub direction: Vec2
+}
 
+/// Fichier tiré par le joueur d'indice donné, pour lui attribuer les points
 #[derive(Component)]
-pub struct FromPlayer;
+pub struct FromPlayer(pub usize);
 
 #[derive(Component)]
 pub struct ScoreBugFix;
@@ -1,4 +1,47 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Color, Component};
 
+use crate::component::weapon::WeaponKind;
+
+/// Fichier poussé, ramassé par le joueur au contact
 #[derive(Component)]
-pub struct PushFile;
+pub struct PushFile {
+    pub power_up: PowerUpKind
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    /// repousse la MEP de quelques secondes
+    ExtraTime,
+    RapidFire,
+    /// les fichiers qui passent ne coutent pas de vie
+    Shield,
+    /// double les points gagnés
+    ScoreBoost,
+    /// détruit tous les ennemis à l'écran
+    Hotfix,
+    Weapon(WeaponKind)
+}
+
+impl PowerUpKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::ExtraTime => "+5s",
+            PowerUpKind::RapidFire => "tir rapide",
+            PowerUpKind::Shield => "bouclier",
+            PowerUpKind::ScoreBoost => "score x2",
+            PowerUpKind::Hotfix => "hotfix !",
+            PowerUpKind::Weapon(kind) => kind.label()
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            PowerUpKind::ExtraTime => Color::CYAN,
+            PowerUpKind::RapidFire => Color::ORANGE,
+            PowerUpKind::Shield => Color::rgb(0.4, 0.6, 1.),
+            PowerUpKind::ScoreBoost => Color::YELLOW,
+            PowerUpKind::Hotfix => Color::rgb(1., 0.3, 0.3),
+            PowerUpKind::Weapon(_) => Color::WHITE
+        }
+    }
+}
@@ -8,4 +8,15 @@ pub struct InGameComponent;
 pub struct InMenuComponent;
 
 #[derive(Component)]
-pub struct InEndgameComponent;
\ No newline at end of file
+pub struct InEndgameComponent;
+
+#[derive(Component)]
+pub struct InHighScoresComponent;
+
+#[derive(Component)]
+pub struct InPauseComponent;
+#[derive(Component)]
+pub struct InReplayComponent;
+
+#[derive(Component)]
+pub struct InControlsComponent;
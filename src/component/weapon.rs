@@ -0,0 +1,109 @@
+use bevy::prelude::Component;
+
+/// Type de fichier tiré par le joueur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaponKind {
+    Scala,
+    /// trois fichiers en éventail
+    RustSpread,
+    /// traverse les ennemis
+    Hotfix,
+    /// lent, se dirige vers l'ennemi le plus proche
+    CodeReview
+}
+
+/// Armes dans l'ordre des touches [1] à [4]
+pub const WEAPONS: [WeaponKind; 4] = [
+    WeaponKind::Scala,
+    WeaponKind::RustSpread,
+    WeaponKind::Hotfix,
+    WeaponKind::CodeReview
+];
+
+impl WeaponKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WeaponKind::Scala => "scala",
+            WeaponKind::RustSpread => "rust x3",
+            WeaponKind::Hotfix => "hotfix",
+            WeaponKind::CodeReview => "code review"
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        WEAPONS.iter().position(|kind| kind == self).unwrap()
+    }
+
+    /// pas de simulation minimum entre deux tirs
+    pub fn fire_interval(&self) -> u32 {
+        match self {
+            WeaponKind::Scala => 8,
+            WeaponKind::RustSpread => 16,
+            WeaponKind::Hotfix => 24,
+            WeaponKind::CodeReview => 30
+        }
+    }
+
+    /// dégâts d'un fichier, à partir de `laser_damage`
+    pub fn damage(&self, base: u32) -> u32 {
+        match self {
+            WeaponKind::Scala | WeaponKind::RustSpread => base,
+            WeaponKind::Hotfix => base * 2,
+            WeaponKind::CodeReview => base * 3
+        }
+    }
+
+    /// multiplie `laser_velocity`
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            WeaponKind::Scala | WeaponKind::RustSpread => 1.,
+            WeaponKind::Hotfix => 1.2,
+            WeaponKind::CodeReview => 0.5
+        }
+    }
+}
+
+/// pas de maintien de la touche de tir avant qu'un tir chargé parte au relachement
+pub const CHARGE_MIN_TICKS: u32 = 20;
+/// pas de maintien pour une charge complète
+pub const CHARGE_FULL_TICKS: u32 = 60;
+
+/// Arme du joueur
+#[derive(Component)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    /// pas restants avant de pouvoir tirer
+    pub cooldown: u32,
+    /// pas pendant lesquels la touche de tir est maintenue
+    pub charge: u32
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Weapon { kind: WeaponKind::Scala, cooldown: 0, charge: 0 }
+    }
+}
+
+impl Weapon {
+    /// part du délai entre deux tirs deja écoulée, 1 quand l'arme est prete
+    pub fn readiness(&self) -> f32 {
+        let interval = self.kind.fire_interval();
+        1. - self.cooldown.min(interval) as f32 / interval as f32
+    }
+
+    /// part de la charge complète, 0 tant que le minimum n'est pas atteint
+    pub fn charge_ratio(&self) -> f32 {
+        if self.charge < CHARGE_MIN_TICKS {
+            return 0.;
+        }
+        (self.charge - CHARGE_MIN_TICKS) as f32 / (CHARGE_FULL_TICKS - CHARGE_MIN_TICKS) as f32
+    }
+
+    /// multiplie la taille et les dégats du tir chargé : de 2 au minimum à 3 à pleine charge
+    pub fn charged_factor(&self) -> Option<f32> {
+        if self.charge < CHARGE_MIN_TICKS {
+            return None;
+        }
+        Some(2. + self.charge_ratio())
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::{Entity, Vec3};
+
+use crate::component::enemy::EnemyKind;
+
+/// Envoyé quand un fichier ennemi tombe à zéro point de vie.
+/// L'entité est deja despawn quand l'évènement est lu.
+pub struct EnemyDestroyed {
+    pub entity: Entity,
+    pub kind: EnemyKind,
+    pub position: Vec3,
+    /// commentaire porté par l'ennemi en mode scan
+    pub source: Option<usize>,
+    /// détruit par un hotfix ramassé : ni fichier poussé ni demi-conflit
+    pub cleared: bool,
+    /// joueur qui a tiré ou ramassé le hotfix
+    pub player: Option<usize>
+}
+
+/// Envoyé au premier impact d'un fichier du joueur sur un ennemi, même s'il survit ;
+/// un hotfix qui en traverse plusieurs ne compte qu'une touche
+pub struct EnemyHit {
+    pub laser: Entity,
+    pub enemy: Entity,
+    pub player: Option<usize>
+}
+
+/// Envoyé à chaque tir d'un joueur
+pub struct ShotFired {
+    pub player: usize
+}
+
+/// Envoyé quand un fichier ennemi passe sous le pc du joueur
+pub struct EnemyEscaped {
+    pub entity: Entity,
+    pub kind: EnemyKind
+}
+
+/// Envoyé quand un fichier du joueur sort de l'écran sans rien toucher
+pub struct LaserMissed;
+
+/// Envoyé quand un fichier ennemi percute le pc du joueur, détruit au choc.
+/// `damage` vaut 0 si le bouclier a encaissé
+pub struct PlayerHit {
+    pub player: usize,
+    pub enemy: Entity,
+    pub kind: EnemyKind,
+    pub damage: u32
+}
@@ -0,0 +1,34 @@
+use std::fs;
+use std::io::ErrorKind;
+
+use bevy::prelude::*;
+use crate::resources::config::{GameConfig, ConfigError};
+
+pub const CONFIG_PATH: &str = "assets/config.ron";
+
+pub fn read_game_config(path: &str) -> Result<GameConfig, ConfigError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ConfigError::Io(format!("{} ({})", path, e)))?;
+    let config: GameConfig = ron::from_str(&content)
+        .map_err(|e| ConfigError::Parse(format!("{} ({})", path, e)))?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Charge la config, ou retombe sur les valeurs par défaut en cas de souci
+pub fn create_game_config(path: &str) -> GameConfig {
+    if let Err(e) = fs::metadata(path) {
+        if e.kind() == ErrorKind::NotFound {
+            info!("pas de fichier {}, config par defaut", path);
+            return GameConfig::default();
+        }
+    }
+
+    match read_game_config(path) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("config {} ignoree, {}", path, e);
+            GameConfig::default()
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use crate::resources::{GameFonts, config::GameConfig};
+
+pub fn create_game_fonts(asset_server: &AssetServer, config: &GameConfig) -> GameFonts {
+    GameFonts {
+        text: asset_server.load(config.font.as_str())
+    }
+}
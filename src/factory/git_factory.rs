@@ -0,0 +1,55 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::resources::sprint::SprintCommit;
+
+// séparateurs du format de `git log`, absents des messages de commit
+const RECORD_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Lit les `count` derniers commits (hors merges) du dépot local, du plus ancien au plus récent
+pub fn read_sprint_commits(repo: &Path, count: usize) -> Result<Vec<SprintCommit>, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["log", "--no-merges", "--shortstat", "--format=%x1e%h%x1f%an%x1f%s"])
+        .arg(format!("-n{}", count))
+        .output()
+        .map_err(|e| format!("git introuvable ({})", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or("erreur inconnue");
+        return Err(format!("{} n'est pas un dépot git lisible ({})", repo.display(), reason.trim()));
+    }
+
+    let mut commits = parse_git_log(&String::from_utf8_lossy(&output.stdout));
+    if commits.is_empty() {
+        return Err(format!("aucun commit dans {}", repo.display()));
+    }
+    commits.reverse();
+    Ok(commits)
+}
+
+/// Découpe la sortie de `git log --shortstat --format=%x1e%h%x1f%an%x1f%s`
+pub fn parse_git_log(output: &str) -> Vec<SprintCommit> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut fields = lines.next()?.splitn(3, FIELD_SEPARATOR);
+            let hash = fields.next()?.trim().to_string();
+            let author = fields.next()?.trim().to_string();
+            let subject = fields.next().unwrap_or("").trim().to_string();
+
+            // " 3 files changed, 10 insertions(+), 2 deletions(-)"
+            let files_changed = lines
+                .find(|line| line.contains("changed"))
+                .and_then(|line| line.split_whitespace().next())
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(0);
+
+            Some(SprintCommit { hash, author, subject, files_changed })
+        })
+        .collect()
+}
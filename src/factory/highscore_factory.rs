@@ -0,0 +1,51 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use crate::resources::highscores::HighScores;
+use crate::storage::data_file;
+
+const HIGH_SCORES_FILE: &str = "highscores.ron";
+
+pub fn high_scores_path() -> PathBuf {
+    data_file(HIGH_SCORES_FILE)
+}
+
+/// Charge le top 10, un fichier absent ou illisible donne un tableau vide
+pub fn create_high_scores() -> HighScores {
+    let path = high_scores_path();
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return HighScores::default(),
+        Err(e) => {
+            warn!("scores {} illisibles ({}), tableau vide", path.display(), e);
+            return HighScores::default();
+        }
+    };
+
+    match ron::from_str::<HighScores>(&content) {
+        Ok(high_scores) => high_scores,
+        Err(e) => {
+            // on met le fichier de côté plutot que de l'écraser à la prochaine sauvegarde
+            let backup = path.with_extension("ron.bak");
+            warn!("scores {} corrompus ({}), sauvegarde dans {}", path.display(), e, backup.display());
+            if let Err(e) = fs::rename(&path, &backup) {
+                warn!("impossible de mettre de cote {} ({})", path.display(), e);
+            }
+            HighScores::default()
+        }
+    }
+}
+
+pub fn save_high_scores(high_scores: &HighScores) -> io::Result<()> {
+    let path = high_scores_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = ron::ser::to_string_pretty(high_scores, ron::ser::PrettyConfig::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    fs::write(path, content)
+}
@@ -1 +1,8 @@
-pub mod texture_factory;
\ No newline at end of file
+pub mod texture_factory;
+pub mod font_factory;
+pub mod config_factory;
+pub mod highscore_factory;
+pub mod replay_factory;
+pub mod scan_factory;
+pub mod git_factory;
+pub mod settings_factory;
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::resources::{MAX_PLAYERS, replay::Replay};
+use crate::storage::data_dir;
+
+pub const LAST_REPLAY_FILE: &str = "derniere-partie.ron";
+
+pub fn replays_dir() -> PathBuf {
+    data_dir().join("replays")
+}
+
+pub fn save_replay(replay: &Replay, file_name: &str) -> io::Result<PathBuf> {
+    let dir = replays_dir();
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(file_name);
+    let content = ron::to_string(replay)
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+pub fn delete_replay(file_name: &str) -> io::Result<()> {
+    fs::remove_file(replays_dir().join(file_name))
+}
+
+/// Le fichier peut avoir été modifié à la main : un nombre de joueurs hors de 1..=MAX_PLAYERS
+/// ne lancerait aucun pc, ou décalerait les touches hors des 32 bits d'un pas
+pub fn load_replay(path: &Path) -> Result<Replay, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("{} illisible ({})", path.display(), e))?;
+    let replay: Replay = ron::from_str(&content)
+        .map_err(|e| format!("{} corrompu ({})", path.display(), e))?;
+    if !(1..=MAX_PLAYERS).contains(&replay.players) {
+        return Err(format!("{} corrompu ({} joueurs)", path.display(), replay.players));
+    }
+    Ok(replay)
+}
+
+/// Replays disponibles, du plus récent au plus ancien
+pub fn list_replays() -> Vec<PathBuf> {
+    let entries = match fs::read_dir(replays_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return vec![]
+    };
+
+    let mut replays = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "ron"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|metadata| metadata.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect::<Vec<_>>();
+
+    replays.sort_by(|a, b| b.0.cmp(&a.0));
+    replays.into_iter().map(|(_, path)| path).collect()
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::resources::scan::SourceComment;
+
+const TAGS: [&str; 4] = ["TODO", "FIXME", "XXX", "HACK"];
+const SOURCE_EXTENSIONS: [&str; 24] = [
+    "rs", "c", "h", "cpp", "hpp", "cs", "go", "java", "kt", "scala", "swift", "py",
+    "rb", "php", "js", "jsx", "ts", "tsx", "vue", "sh", "lua", "ex", "sql", "css"
+];
+// dossiers de dépendances ou de build, sans intéret pour une rétro
+const SKIPPED_DIRS: [&str; 5] = ["target", "node_modules", "vendor", "dist", "build"];
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+const MAX_COMMENTS: usize = 1000;
+
+/// Parcourt `root` et relève les commentaires TODO/FIXME/XXX/HACK des fichiers sources,
+/// dans l'ordre alphabétique des chemins pour qu'un scan donne toujours le meme résultat
+pub fn scan_source_tree(root: &Path) -> Result<Vec<SourceComment>, String> {
+    if !root.is_dir() {
+        return Err(format!("{} n'est pas un dossier", root.display()));
+    }
+
+    let mut comments = vec![];
+    let mut files = vec![];
+    collect_source_files(root, &mut files)
+        .map_err(|e| format!("{} illisible ({})", root.display(), e))?;
+
+    for file in files {
+        if comments.len() >= MAX_COMMENTS {
+            break;
+        }
+        // fichier binaire, trop gros ou illisible : ignoré
+        let content = match fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(_) => continue
+        };
+        let path = file.strip_prefix(root).unwrap_or(&file).display().to_string();
+
+        for (index, line) in content.lines().enumerate() {
+            if let Some((tag, text)) = find_tag(line) {
+                comments.push(SourceComment {
+                    path: path.clone(),
+                    line: index as u32 + 1,
+                    tag: tag.to_string(),
+                    text: text.to_string()
+                });
+            }
+        }
+    }
+
+    comments.truncate(MAX_COMMENTS);
+    Ok(comments)
+}
+
+fn collect_source_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(_) => continue
+        };
+
+        if file_type.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                // un sous-dossier illisible n'empeche pas de scanner le reste
+                let _ = collect_source_files(&path, files);
+            }
+        } else if file_type.is_file() && is_source_file(&path, &entry) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_source_file(path: &Path, entry: &fs::DirEntry) -> bool {
+    let extension = path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+    let is_source = extension.map_or(false, |ext| SOURCE_EXTENSIONS.contains(&ext.as_str()));
+    let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(u64::MAX);
+
+    is_source && size <= MAX_FILE_SIZE
+}
+
+/// Premier tag de la ligne écrit comme un mot entier, avec le texte qui le suit
+pub fn find_tag(line: &str) -> Option<(&'static str, &str)> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    TAGS.iter()
+        .filter_map(|&tag| {
+            line.match_indices(tag)
+                .find(|&(start, _)| {
+                    let before = line[..start].chars().next_back();
+                    let after = line[start + tag.len()..].chars().next();
+                    !before.map_or(false, is_word) && !after.map_or(false, is_word)
+                })
+                .map(|(start, _)| (start, tag))
+        })
+        .min_by_key(|&(start, _)| start)
+        .map(|(start, tag)| {
+            let text = line[start + tag.len()..]
+                .trim_start_matches(|c: char| c == ':' || c == '-' || c.is_whitespace())
+                .trim_end()
+                .trim_end_matches("*/")
+                .trim_end();
+            (tag, text)
+        })
+}
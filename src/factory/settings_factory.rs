@@ -0,0 +1,52 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use crate::resources::keymap::{KeyMap, Settings};
+use crate::storage::data_file;
+
+const SETTINGS_FILE: &str = "reglages.ron";
+
+pub fn settings_path() -> PathBuf {
+    data_file(SETTINGS_FILE)
+}
+
+/// Charge les touches de l'utilisateur, les touches par défaut si le fichier est absent ou illisible
+pub fn create_key_map() -> KeyMap {
+    let path = settings_path();
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return KeyMap::default(),
+        Err(e) => {
+            warn!("reglages {} illisibles ({}), touches par defaut", path.display(), e);
+            return KeyMap::default();
+        }
+    };
+
+    let settings = match ron::from_str::<Settings>(&content) {
+        Ok(settings) => settings,
+        Err(e) => {
+            warn!("reglages {} mal formes ({}), touches par defaut", path.display(), e);
+            return KeyMap::default();
+        }
+    };
+
+    let (key_map, ignored) = KeyMap::from_settings(&settings);
+    for name in ignored {
+        warn!("touche {} inconnue ou en double dans {}, ignoree", name, path.display());
+    }
+    key_map
+}
+
+pub fn save_key_map(key_map: &KeyMap) -> io::Result<()> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let content = ron::ser::to_string_pretty(&key_map.settings(), ron::ser::PrettyConfig::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    fs::write(path, content)
+}
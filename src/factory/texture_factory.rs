@@ -1,18 +1,21 @@
 use bevy::prelude::*;
 use crate::GameTextures;
+use crate::resources::config::GameConfig;
 
-const COMPUTER_SPRITE: &str = "pc-codeur.png";
-const ENEMY_SPRITE: &str = "fixme-file.png";
-const FILE_LASER_SPRITE: &str = "scala-file.png";
-const PUSH_FILE_SPRITE: &str = "push-file.png";
-const BG: &str = "bg.png";
-
-pub fn create_game_textures(asset_server: &AssetServer) -> GameTextures {
+pub fn create_game_textures(asset_server: &AssetServer, config: &GameConfig) -> GameTextures {
+    let textures = &config.textures;
     GameTextures {
-        computer: asset_server.load(COMPUTER_SPRITE),
-        file_laser: asset_server.load(FILE_LASER_SPRITE),
-        fixme_file: asset_server.load(ENEMY_SPRITE),
-        push_file: asset_server.load(PUSH_FILE_SPRITE),
-        bg: asset_server.load(BG)
+        computer: asset_server.load(textures.computer.as_str()),
+        file_laser: asset_server.load(textures.file_laser.as_str()),
+        rust_file: asset_server.load(textures.rust_file.as_str()),
+        hotfix_file: asset_server.load(textures.hotfix_file.as_str()),
+        code_review_file: asset_server.load(textures.code_review_file.as_str()),
+        fixme_file: asset_server.load(textures.fixme_file.as_str()),
+        flaky_test: asset_server.load(textures.flaky_test.as_str()),
+        merge_conflict: asset_server.load(textures.merge_conflict.as_str()),
+        legacy_code: asset_server.load(textures.legacy_code.as_str()),
+        null_pointer: asset_server.load(textures.null_pointer.as_str()),
+        push_file: asset_server.load(textures.push_file.as_str()),
+        bg: asset_server.load(textures.bg.as_str())
     }
 }
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::{AppState, build_game_app};
+use crate::component::{enemy::Enemy, player::{Player, PlayerInput}};
+use crate::options::{InputPolicy, LaunchOptions};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::{EndReason, MAX_PLAYERS, Scoring, config::GameConfig};
+
+const DEFAULT_ROUNDS: u32 = 100;
+// pas entre deux tirs du joueur scripté
+const SCRIPTED_FIRE_COOLDOWN: u32 = 12;
+// pas pendant lesquels le joueur aléatoire garde la meme direction
+const RANDOM_MOVE_TICKS: u32 = 30;
+const RANDOM_FIRE_CHANCE: f64 = 0.1;
+
+/// Etat propre à chaque pc commandé, en coop les deux joueurs ne tirent pas au meme rythme
+#[derive(Default, Clone, Copy)]
+struct BotState {
+    move_ticks: u32,
+    fire_cooldown: u32
+}
+
+/// Joueur simulé, resemé à chaque manche pour que la simulation soit reproductible
+struct HeadlessPlayer {
+    policy: InputPolicy,
+    rng: StdRng,
+    bots: [BotState; MAX_PLAYERS]
+}
+
+impl HeadlessPlayer {
+    fn new(policy: InputPolicy) -> Self {
+        HeadlessPlayer {
+            policy,
+            rng: StdRng::seed_from_u64(0),
+            bots: Default::default()
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.bots = Default::default();
+    }
+}
+
+struct RoundResult {
+    seed: u64,
+    points: u32,
+    hits: u32,
+    shots_fired: u32,
+    escaped: u32,
+    shipped: bool
+}
+
+/// Joue `--rounds` manches sans fenetre, aussi vite que possible, puis affiche les statistiques
+pub fn run_headless(options: LaunchOptions) {
+    let rounds = options.rounds.unwrap_or(DEFAULT_ROUNDS);
+    let base_seed = options.seed.unwrap_or_else(rand::random);
+    let policy = options.policy;
+
+    let mut app = build_game_app(options);
+    app
+        .insert_resource(HeadlessPlayer::new(policy))
+        .add_system_set_to_stage(
+            FixedUpdateStage,
+            SystemSet::new()
+                .with_run_criteria(run_if_in_game)
+                .after(SimulationLabel::Restore)
+                .with_system(headless_input_system.label(SimulationLabel::Input))
+        );
+
+    let mut results = Vec::with_capacity(rounds as usize);
+    for round in 0..rounds {
+        let seed = base_seed.wrapping_add(round as u64);
+        app.world.resource_mut::<LaunchOptions>().seed = Some(seed);
+        app.world.resource_mut::<HeadlessPlayer>().reseed(seed);
+        if round > 0 {
+            app.world.resource_mut::<State<AppState>>().set(AppState::InGame).unwrap();
+        }
+
+        loop {
+            app.update();
+            if *app.world.resource::<State<AppState>>().current() == AppState::EndGame {
+                break;
+            }
+        }
+
+        let scoring = app.world.resource::<Scoring>();
+        results.push(RoundResult {
+            seed,
+            points: scoring.points,
+            hits: scoring.hits,
+            shots_fired: scoring.shots_fired,
+            escaped: scoring.escaped,
+            shipped: *app.world.resource::<EndReason>() == EndReason::Shipped
+        });
+    }
+
+    print_stats(policy, &results);
+}
+
+fn headless_input_system(
+    config: Res<GameConfig>,
+    mut player: ResMut<HeadlessPlayer>,
+    mut query: Query<(&Player, &Transform, &mut PlayerInput)>,
+    enemy_query: Query<&Transform, With<Enemy>>
+) {
+    let player = &mut *player;
+    // le tirage aléatoire est partagé, l'ordre des joueurs doit rester le meme d'une manche à l'autre
+    let mut pcs = query.iter_mut().collect::<Vec<_>>();
+    pcs.sort_by_key(|(pc, _, _)| pc.index);
+
+    for (pc, player_tf, mut input) in pcs {
+        let bot = &mut player.bots[pc.index];
+
+        match player.policy {
+            InputPolicy::Random => {
+                if bot.move_ticks == 0 {
+                    bot.move_ticks = RANDOM_MOVE_TICKS;
+                    input.x = [-1., 0., 1.][player.rng.gen_range(0..3)];
+                }
+                bot.move_ticks -= 1;
+                input.fire = player.rng.gen_bool(RANDOM_FIRE_CHANCE);
+            }
+            InputPolicy::Scripted => {
+                // l'ennemi le plus proche de passer sous le pc
+                let target = enemy_query
+                    .iter()
+                    .min_by(|a, b| a.translation.y.total_cmp(&b.translation.y));
+
+                bot.fire_cooldown = bot.fire_cooldown.saturating_sub(1);
+                input.x = 0.;
+                input.fire = false;
+
+                if let Some(target_tf) = target {
+                    let dx = target_tf.translation.x - player_tf.translation.x;
+                    let aligned = dx.abs() < config.sprite_size.0 * config.sprite_scale / 2.;
+
+                    if !aligned {
+                        input.x = dx.signum();
+                    } else if bot.fire_cooldown == 0 {
+                        input.fire = true;
+                        bot.fire_cooldown = SCRIPTED_FIRE_COOLDOWN;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn print_stats(policy: InputPolicy, results: &[RoundResult]) {
+    let rounds = results.len();
+    let mut points = results.iter().map(|result| result.points).collect::<Vec<u32>>();
+    points.sort_unstable();
+    let percentile = |p: usize| points[(rounds - 1) * p / 100];
+
+    let mean = points.iter().map(|&p| p as f64).sum::<f64>() / rounds as f64;
+    let hits = results.iter().map(|result| result.hits).sum::<u32>();
+    let shots_fired = results.iter().map(|result| result.shots_fired).sum::<u32>();
+    let escaped = results.iter().map(|result| result.escaped).sum::<u32>();
+    let shipped = results.iter().filter(|result| result.shipped).count();
+    let accuracy = if shots_fired == 0 { 0. } else { hits as f64 / shots_fired as f64 * 100. };
+
+    println!("{} manches simulees, joueur {:?}, seeds {} a {}", rounds, policy, results[0].seed, results[rounds - 1].seed);
+    println!("score : moyenne {:.1}, min {}, p25 {}, mediane {}, p75 {}, max {}",
+        mean, points[0], percentile(25), percentile(50), percentile(75), points[rounds - 1]);
+    println!("precision : {:.1}% ({}/{})", accuracy, hits, shots_fired);
+    println!("ennemis passes : {} ({:.2} par manche)", escaped, escaped as f64 / rounds as f64);
+    println!("MEP livrees : {}/{}", shipped, rounds);
+}
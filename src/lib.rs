@@ -0,0 +1,205 @@
+pub mod plugins;
+pub mod resources;
+pub mod component;
+pub mod factory;
+pub mod states;
+pub mod events;
+pub mod storage;
+pub mod options;
+pub mod headless;
+
+use bevy::prelude::*;
+use bevy::input::InputPlugin;
+// use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
+
+use plugins::{
+    player::PlayerPlugin,
+    enemy::EnemyPlugin,
+    collide_plugin::CollideFireEnemiesPlugin,
+    endgame_plugin::EndgamePlugin,
+    menu_plugin::MenuPlugin,
+    ingame_plugin::IngamePlugin,
+    config_plugin::ConfigPlugin,
+    scoring_plugin::ScoringPlugin,
+    highscore_plugin::HighScorePlugin,
+    pause_plugin::PausePlugin,
+    fixed_step_plugin::FixedStepPlugin,
+    replay_plugin::ReplayPlugin,
+    scan_plugin::ScanPlugin,
+    sprint_plugin::SprintPlugin,
+    power_up_plugin::PowerUpPlugin,
+    controls_plugin::ControlsPlugin,
+    gamepad_plugin::GamepadPlugin
+};
+use resources::{Timer as MonTimer, WinSize, GameTextures, GameFonts, Scoring, Lives, EndReason, GameMode, GameRng, PlayerCount, MAX_PLAYERS};
+use resources::config::GameConfig;
+use resources::replay::ReplayPlayback;
+use resources::keymap::KeyMap;
+use resources::scan::ScanComments;
+use resources::sprint::Sprint;
+use factory::{
+    texture_factory::create_game_textures,
+    font_factory::create_game_fonts,
+    scan_factory::scan_source_tree,
+    git_factory::read_sprint_commits,
+    config_factory::{create_game_config, CONFIG_PATH}
+};
+use states::AppState;
+use options::LaunchOptions;
+
+// region constantes
+// les réglages modifiables sont dans assets/config.ron (voir GameConfig)
+// durée d'un pas de simulation, indépendante du framerate
+pub const TIME_STEP: f32 = 1. / 60.;
+// même taille que la fenetre, utilisée aussi sans fenetre
+const WIN_SIZE: (f32, f32) = (600., 600.);
+// commits lus par `--sprint` sans `--commits`
+const DEFAULT_SPRINT_COMMITS: usize = 20;
+// endregion
+
+/// Construit l'application du jeu, avec fenetre ou, si `options.headless`,
+/// seulement la simulation d'une manche (sans rendu, assets ni entrée de l'utilisateur) :
+/// une manche démarre au premier `App::update` et chaque update joue un pas.
+/// Les réglages de `assets/config.ron` peuvent etre remplacés avec
+/// `app.insert_resource(config)` avant le premier update.
+pub fn build_game_app(options: LaunchOptions) -> App {
+    let headless = options.headless;
+    let config = create_game_config(CONFIG_PATH);
+    let scan_comments = options.scan.as_ref().and_then(|root| load_scan_comments(root));
+    let sprint = options.sprint.as_ref().and_then(|repo| load_sprint(repo, options.commits.unwrap_or(DEFAULT_SPRINT_COMMITS)));
+    let game_mode = if scan_comments.is_some() {
+        GameMode::Scan
+    } else if sprint.is_some() {
+        GameMode::Sprint
+    } else {
+        GameMode::Classic
+    };
+
+    let player_count = PlayerCount(if options.coop { MAX_PLAYERS } else { 1 });
+
+    let mut app = App::new();
+    app
+        .insert_resource(options)
+        .insert_resource(player_count)
+        .insert_resource(GameRng::new(0))
+        .insert_resource(Scoring::default())
+        .insert_resource(MonTimer(config.round_time))
+        .insert_resource(Lives(config.lives))
+        .insert_resource(EndReason::Shipped)
+        .insert_resource(game_mode)
+        .insert_resource(config);
+
+    if let Some(scan_comments) = scan_comments {
+        app.insert_resource(scan_comments);
+    }
+    if let Some(sprint) = sprint {
+        app.insert_resource(sprint);
+    }
+
+    if headless {
+        app
+            .insert_resource(WinSize { w: WIN_SIZE.0, h: WIN_SIZE.1 })
+            // aucune image ni police n'est chargée, les entités gardent des handles vides
+            .insert_resource(GameTextures::default())
+            .insert_resource(GameFonts::default())
+            .insert_resource(ReplayPlayback::default())
+            // les touches de l'utilisateur ne sont pas lues, la simulation reste reproductible
+            .insert_resource(KeyMap::default())
+            .add_plugins(MinimalPlugins)
+            .add_plugin(InputPlugin)
+            .add_plugin(GamepadPlugin)
+            .add_state(AppState::InGame)
+            .add_plugin(FixedStepPlugin { step_every_update: true })
+            .add_plugin(IngamePlugin)
+            .add_plugin(PlayerPlugin)
+            .add_plugin(EnemyPlugin)
+            .add_plugin(CollideFireEnemiesPlugin)
+            .add_plugin(ScoringPlugin)
+            .add_plugin(PowerUpPlugin)
+            // la pause ne s'ouvre qu'avec une touche, les tests s'en servent pour relancer la manche
+            .add_plugin(PausePlugin)
+            .add_plugin(ScanPlugin)
+            .add_plugin(SprintPlugin);
+        return app;
+    }
+
+    app
+        .insert_resource(ClearColor(Color::rgb(0.04, 0.04, 0.04)))
+        .insert_resource(WindowDescriptor {title: "Foyer MEP horror".to_string(),width: WIN_SIZE.0,height: WIN_SIZE.1,..Default::default()})
+        .add_plugins(DefaultPlugins)
+        // .add_plugin(LogDiagnosticsPlugin::default())
+        // .add_plugin(FrameTimeDiagnosticsPlugin::default())
+        .add_startup_system(setup_system)
+        .add_state(AppState::MainMenu) // state de départ
+        // doit précéder les plugins qui ajoutent des systèmes au stage à pas fixe
+        .add_plugin(FixedStepPlugin::default())
+        .add_plugin(EndgamePlugin)
+        .add_plugin(IngamePlugin)
+        .add_plugin(PlayerPlugin)
+        .add_plugin(EnemyPlugin)
+        .add_plugin(MenuPlugin)
+        .add_plugin(CollideFireEnemiesPlugin)
+        .add_plugin(ConfigPlugin)
+        .add_plugin(ScoringPlugin)
+        .add_plugin(PowerUpPlugin)
+        .add_plugin(HighScorePlugin)
+        .add_plugin(PausePlugin)
+        .add_plugin(ReplayPlugin)
+        .add_plugin(ControlsPlugin)
+        .add_plugin(GamepadPlugin)
+        .add_plugin(ScanPlugin)
+        .add_plugin(SprintPlugin);
+    app
+}
+
+// le jeu se lance en mode classique si le dossier ne donne aucun commentaire
+fn load_scan_comments(root: &std::path::Path) -> Option<ScanComments> {
+    match scan_source_tree(root) {
+        Ok(comments) if comments.is_empty() => {
+            eprintln!("aucun TODO/FIXME/XXX/HACK dans {}, partie classique", root.display());
+            None
+        }
+        Ok(comments) => {
+            eprintln!("{} commentaires trouves dans {}", comments.len(), root.display());
+            Some(ScanComments::new(comments))
+        }
+        Err(e) => {
+            eprintln!("scan impossible : {}, partie classique", e);
+            None
+        }
+    }
+}
+
+fn setup_system(
+    mut commands: Commands, 
+    asset_server: Res<AssetServer>,
+    mut windows: ResMut<Windows>,
+    config: Res<GameConfig>
+) {
+    // camera
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    // on recupere la taille de la fenetre
+    let window = windows.get_primary_mut().unwrap();
+    let (win_w, win_h) = (window.width(), window.height());
+    let win_size = WinSize {w: win_w, h: win_h};
+    commands.insert_resource(win_size);
+
+    let game_textures = create_game_textures(&asset_server, &config);
+    commands.insert_resource(game_textures);
+    commands.insert_resource(create_game_fonts(&asset_server, &config));
+}
+
+// le jeu se lance en mode classique si le dépot est illisible
+fn load_sprint(repo: &std::path::Path, count: usize) -> Option<Sprint> {
+    match read_sprint_commits(repo, count) {
+        Ok(commits) => {
+            eprintln!("sprint de {} commits depuis {}", commits.len(), repo.display());
+            Some(Sprint::new(commits))
+        }
+        Err(e) => {
+            eprintln!("sprint impossible : {}, partie classique", e);
+            None
+        }
+    }
+}
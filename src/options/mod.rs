@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+/// Options passées en ligne de commande au lancement du jeu
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchOptions {
+    /// graine imposée pour toutes les manches (`--seed 42`)
+    pub seed: Option<u64>,
+    /// simulation sans fenetre ni assets (`--headless`)
+    pub headless: bool,
+    /// nombre de manches simulées (`--rounds 100`)
+    pub rounds: Option<u32>,
+    /// commandes du joueur simulé (`--policy random|scripted`)
+    pub policy: InputPolicy,
+    /// dossier dont les TODO/FIXME deviennent les ennemis (`--scan ../mon-projet`)
+    pub scan: Option<PathBuf>,
+    /// dépot git dont les derniers commits deviennent les vagues (`--sprint ../mon-projet`)
+    pub sprint: Option<PathBuf>,
+    /// nombre de commits lus pour le sprint (`--commits 20`)
+    pub commits: Option<usize>,
+    /// deux joueurs sur le meme clavier (`--coop`)
+    pub coop: bool
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputPolicy {
+    /// déplacements et tirs au hasard
+    Random,
+    /// vise l'ennemi le plus bas et tire quand il est aligné
+    Scripted
+}
+
+impl Default for InputPolicy {
+    fn default() -> Self {
+        InputPolicy::Scripted
+    }
+}
+
+impl LaunchOptions {
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = LaunchOptions::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("--seed attend un nombre")?;
+                    let seed = value.parse::<u64>()
+                        .map_err(|_| format!("--seed attend un nombre (recu {})", value))?;
+                    options.seed = Some(seed);
+                }
+                "--headless" => options.headless = true,
+                "--coop" => options.coop = true,
+                "--rounds" => {
+                    let value = args.next().ok_or("--rounds attend un nombre")?;
+                    let rounds = value.parse::<u32>()
+                        .ok()
+                        .filter(|&rounds| rounds > 0)
+                        .ok_or(format!("--rounds attend un nombre positif (recu {})", value))?;
+                    options.rounds = Some(rounds);
+                }
+                "--policy" => {
+                    let value = args.next().ok_or("--policy attend random ou scripted")?;
+                    options.policy = match value.as_str() {
+                        "random" => InputPolicy::Random,
+                        "scripted" => InputPolicy::Scripted,
+                        other => return Err(format!("--policy attend random ou scripted (recu {})", other))
+                    };
+                }
+                "--scan" => {
+                    let value = args.next().ok_or("--scan attend un dossier")?;
+                    options.scan = Some(PathBuf::from(value));
+                }
+                "--sprint" => {
+                    let value = args.next().ok_or("--sprint attend un dépot git")?;
+                    options.sprint = Some(PathBuf::from(value));
+                }
+                "--commits" => {
+                    let value = args.next().ok_or("--commits attend un nombre")?;
+                    let commits = value.parse::<usize>()
+                        .ok()
+                        .filter(|&commits| commits > 0)
+                        .ok_or(format!("--commits attend un nombre positif (recu {})", value))?;
+                    options.commits = Some(commits);
+                }
+                other => return Err(format!("option inconnue : {}", other))
+            }
+        }
+
+        if options.scan.is_some() && options.sprint.is_some() {
+            return Err("--scan et --sprint ne peuvent pas etre utilisés ensemble".to_string());
+        }
+        if options.commits.is_some() && options.sprint.is_none() {
+            return Err("--commits n'a de sens qu'avec --sprint".to_string());
+        }
+
+        Ok(options)
+    }
+}
@@ -1,194 +1,174 @@
 use std::collections::HashSet;
-use std::iter::FromIterator;
-
-use rand::prelude::*;
 
 use bevy::prelude::*;
 use bevy::math::Vec3Swizzles;
 use bevy::sprite::collide_aabb::collide;
 
-use crate::{Scoring, SPRITE_SIZE, POLICE};
 use crate::component::{
-    enemy::Enemy,
+    enemy::{Enemy, EnemyKind, SourceLabel},
+    health::{Health, Damage, HitFlash, Invulnerability},
+    player::{Player, FromPlayer},
     sprite_size::SpriteSize,
-    laser::Laser,
-    player::ScoreBugFix,
-    velocity::Velocity,
-    movable::Movable,
-    push_file::PushFile,
-    state::InGameComponent,
-    temporary::Temporary
-};
-use crate::resources::{
-    GameTextures,
-    WinSize
+    laser::{Laser, Piercing}
 };
+use crate::events::{EnemyDestroyed, EnemyHit, PlayerHit};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::PowerUps;
+
+// pas d'invulnérabilité du pc après un choc
+const INVULNERABILITY_TICKS: u32 = 90;
+
+// un ennemi détruit par un tir dans ce pas ne percute plus le pc
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct LaserCollisionLabel;
 
 pub struct CollideFireEnemiesPlugin;
 
 impl Plugin for CollideFireEnemiesPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_system(player_file_hit_enemy_system);
+            .add_event::<EnemyDestroyed>()
+            .add_event::<PlayerHit>()
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Movement)
+                    .with_system(player_file_hit_enemy_system.label(SimulationLabel::Collision).label(LaserCollisionLabel))
+                    .with_system(enemy_hit_player_system.label(SimulationLabel::Collision).after(LaserCollisionLabel))
+            )
+            .add_system(hit_flash_system);
     }
 }
 
+pub fn is_colliding(a_tf: &Transform, a_size: &SpriteSize, b_tf: &Transform, b_size: &SpriteSize) -> bool {
+    let a_scale = Vec2::from(a_tf.scale.xy());
+    let b_scale = Vec2::from(b_tf.scale.xy());
+
+    collide(
+        a_tf.translation,
+        a_size.0 * a_scale,
+        b_tf.translation,
+        b_size.0 * b_scale
+    ).is_some()
+}
+
+#[allow(clippy::type_complexity)]
 fn player_file_hit_enemy_system(
     mut commands: Commands,
-    win_size: Res<WinSize>,
-    mut scoring: ResMut<Scoring>,
-    asset_server: Res<AssetServer>,
-    game_textures: Res<GameTextures>,
-    laser_query: Query<(Entity, &Transform, &SpriteSize), With<Laser>>,
-    enemy_query: Query<(Entity, &Transform, &SpriteSize), With<Enemy>>,
-    mut score_bug_fix_query: Query<(Entity, &mut Text), With<ScoreBugFix>>
+    mut enemy_destroyed: EventWriter<EnemyDestroyed>,
+    mut enemy_hit: EventWriter<EnemyHit>,
+    mut laser_query: Query<(Entity, &Transform, &SpriteSize, &Damage, Option<&mut Piercing>, Option<&FromPlayer>), With<Laser>>,
+    mut enemy_query: Query<(Entity, &Transform, &SpriteSize, &EnemyKind, &mut Health, &mut HitFlash, Option<&SourceLabel>), With<Enemy>>
 ) {
-    laser_query
-        .iter()
-        .fold(
-            HashSet::new(), 
-            |acc, current| 
-            handle_collide_laser_enemies(&mut commands, &win_size, &mut scoring, &asset_server, &acc, current, &game_textures, &enemy_query, &mut score_bug_fix_query)
-        );
-}
+    // ce set permet de ne pas delete deux fois une meme entité dans le cas d'une collision double
+    // (évite un warning de bevy)
+    let mut despawned_entities: HashSet<Entity> = HashSet::new();
 
-fn handle_collide_laser_enemies(
-    commands: &mut Commands,
-    win_size: &Res<WinSize>,
-    mut scoring: &mut ResMut<Scoring>,
-    asset_server: &Res<AssetServer>,
-    despawned_entities: &HashSet<Entity>, 
-    laser_info: (Entity, &Transform, &SpriteSize),
-    game_textures: &Res<GameTextures>,
-    enemy_query: &Query<(Entity, &Transform, &SpriteSize), With<Enemy>>,
-    score_bug_fix_query: &mut Query<(Entity, &mut Text), With<ScoreBugFix>>
-) -> HashSet<Entity> {
-    enemy_query
-        .iter()
-        .fold(
-            despawned_entities.clone(), 
-            |acc: HashSet<Entity>, current| 
-            handle_collide_laser_enemy(commands, win_size, &mut scoring, asset_server, acc, laser_info, current, game_textures, score_bug_fix_query)
-        )
-}
+    for (laser_entity, laser_tf, laser_size, laser_damage, mut piercing, from_player) in laser_query.iter_mut() {
+        let player = from_player.map(|from_player| from_player.0);
+        for (enemy_entity, enemy_tf, enemy_size, enemy_kind, mut enemy_health, mut enemy_flash, source_label) in enemy_query.iter_mut() {
+            // un fichier deja consommé par un autre ennemi ne touche plus rien
+            if despawned_entities.contains(&laser_entity) {
+                break;
+            }
+            if despawned_entities.contains(&enemy_entity) || !is_colliding(laser_tf, laser_size, enemy_tf, enemy_size) {
+                continue;
+            }
+
+            // un hotfix traverse l'ennemi mais ne le touche qu'une fois
+            let first_impact = if let Some(piercing) = piercing.as_mut() {
+                if piercing.hit.contains(&enemy_entity) {
+                    continue;
+                }
+                piercing.hit.push(enemy_entity);
+                piercing.hit.len() == 1
+            } else {
+                // remove laser
+                commands.entity(laser_entity).despawn();
+                despawned_entities.insert(laser_entity);
+                true
+            };
 
-fn handle_collide_laser_enemy(
-    commands: &mut Commands,
-    win_size: &Res<WinSize>,
-    scoring: &mut ResMut<Scoring>,
-    asset_server: &Res<AssetServer>,
-    despawned_entities: HashSet<Entity>, 
-    laser_info: (Entity, &Transform, &SpriteSize),
-    enemy_info: (Entity, &Transform, &SpriteSize),
-    game_textures: &Res<GameTextures>,
-    score_bug_fix_query: &mut Query<(Entity, &mut Text), With<ScoreBugFix>>
-) -> HashSet<Entity> {
-    let (laser_entity, laser_tf, laser_size) = laser_info;
-    let (enemy_entity, enemy_tf, enemy_size) = enemy_info;
-
-    let laser_scale = Vec2::from(laser_tf.scale.xy());
-    let enemy_scale = Vec2::from(enemy_tf.scale.xy());
-
-    let collision = collide(
-        laser_tf.translation,
-        laser_size.0 * laser_scale,
-        enemy_tf.translation,
-        enemy_size.0 * enemy_scale
-    );
-
-    if let Some(_) = collision {
-        // ces checks permettent de ne pas delete deux fois une meme entité dans le cas d'une collision double
-        // (évite un warning de bevy)
-        //remove enemy
-        let despawn_plus_enemy: HashSet<Entity> = if !despawned_entities.contains(&enemy_entity) {
-            commands.entity(enemy_entity).despawn();
-            scoring.bug_fix += 1;
-            spawn_push_file(commands, enemy_tf, game_textures);
-            spawn_push_text(commands, asset_server, win_size);
-            
-            for (_, mut text) in score_bug_fix_query.iter_mut() {
-                text.sections[0].value = format!("bug fix : {}", scoring.bug_fix).to_string();
+            enemy_health.current = enemy_health.current.saturating_sub(laser_damage.0);
+            enemy_flash.trigger();
+            // un tir ne compte qu'une touche, sinon la précision d'un hotfix dépasserait 100%
+            if first_impact {
+                enemy_hit.send(EnemyHit { laser: laser_entity, enemy: enemy_entity, player });
             }
-            
-            despawned_entities
-                .union(&HashSet::from_iter(vec![enemy_entity]))
-                .map(|a| a.clone())
-                .collect()
-        } else {
-            despawned_entities
-        };
-        
-        // remove laser
-        if !despawn_plus_enemy.contains(&laser_entity) {
-            commands.entity(laser_entity).despawn();
-            
-            despawn_plus_enemy
-                .union(&HashSet::from_iter(vec![laser_entity]))
-                .map(|a| a.clone())
-                .collect::<HashSet<Entity>>()
 
-        } else {
-            despawn_plus_enemy
-        }        
-    } else {
-        despawned_entities
+            //remove enemy
+            if enemy_health.current == 0 {
+                commands.entity(enemy_entity).despawn_recursive();
+                despawned_entities.insert(enemy_entity);
+
+                enemy_destroyed.send(EnemyDestroyed {
+                    entity: enemy_entity,
+                    kind: *enemy_kind,
+                    position: enemy_tf.translation,
+                    source: source_label.map(|label| label.0),
+                    cleared: false,
+                    player
+                });
+            }
+        }
     }
 }
 
-fn spawn_push_file(
-    commands: &mut Commands,
-    enemy_position: &Transform,
-    game_textures: &Res<GameTextures>
+#[allow(clippy::type_complexity)]
+fn enemy_hit_player_system(
+    mut commands: Commands,
+    mut player_hit: EventWriter<PlayerHit>,
+    power_ups: Res<PowerUps>,
+    mut player_query: Query<(&Player, &Transform, &SpriteSize, &mut Health, &mut Invulnerability)>,
+    mut enemy_query: Query<(Entity, &Transform, &SpriteSize, &EnemyKind, &mut Health), (With<Enemy>, Without<Player>)>
 ) {
-    commands
-        .spawn_bundle(SpriteBundle {
-            transform: Transform {
-                translation: Vec3::new(enemy_position.translation.x, enemy_position.translation.y, 10.),
-                ..Default::default()
-            },
-            texture: game_textures.push_file.clone(),
-            ..Default::default()
-        })
-        .insert(SpriteSize::from(SPRITE_SIZE))
-        .insert(Velocity {x: 0., y: 0.8})
-        .insert(Movable { auto_despawn: true })
-        .insert(PushFile)
-        .insert(InGameComponent);
+    for (player, player_tf, player_size, mut player_health, mut invulnerability) in player_query.iter_mut() {
+        // un pc crashé en coop ne bloque plus rien
+        if player_health.current == 0 {
+            continue;
+        }
+        invulnerability.ticks = invulnerability.ticks.saturating_sub(1);
+        if invulnerability.is_active() {
+            continue;
+        }
+
+        for (enemy_entity, enemy_tf, enemy_size, enemy_kind, mut enemy_health) in enemy_query.iter_mut() {
+            if enemy_health.current == 0 || !is_colliding(player_tf, player_size, enemy_tf, enemy_size) {
+                continue;
+            }
+
+            // le fichier s'écrase sur le pc, sans rapporter de points
+            enemy_health.current = 0;
+            commands.entity(enemy_entity).despawn_recursive();
+
+            // le bouclier encaisse le choc à la place du pc
+            let damage = if power_ups.shield > 0 { 0 } else { 1 };
+            player_health.current = player_health.current.saturating_sub(damage);
+            if damage > 0 {
+                invulnerability.ticks = INVULNERABILITY_TICKS;
+            }
+
+            player_hit.send(PlayerHit { player: player.index, enemy: enemy_entity, kind: *enemy_kind, damage });
+
+            if invulnerability.is_active() {
+                break;
+            }
+        }
+    }
 }
 
-fn spawn_push_text(
-    commands: &mut Commands, 
-    asset_server: &Res<AssetServer>,
-    win_size: &Res<WinSize>
+fn hit_flash_system(
+    time: Res<Time>,
+    mut query: Query<(&mut Sprite, &mut HitFlash)>
 ) {
-    let mut rng = thread_rng();
-    let w_span = win_size.w / 2. - 64.;
-    let h_span = win_size.h / 2. - 64.;
-    let x = rng.gen_range(-w_span..w_span);
-    let y = rng.gen_range(-h_span..h_span);// rng.gen_range(-h_span..h_span);
-
-    commands
-        .spawn_bundle(Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: "PUSH !!".to_string(),
-                    style: TextStyle {
-                        color: Color::rgb(0., 0.3, 0.),
-                        font: asset_server.load(POLICE),
-                        font_size: 36.
-                    }
-                }],
-                
-                alignment: TextAlignment {
-                    vertical: VerticalAlign::Center,
-                    horizontal: HorizontalAlign::Center
-                },
-                
-                ..Default::default()
-            },
-            transform: Transform::from_xyz(x, y, 2.),
-            ..Default::default()
-        })
-        .insert(Temporary { duration: 1., current_time: 0.})
-        .insert(InGameComponent);
+    for (mut sprite, mut flash) in query.iter_mut().filter(|(_, flash)| flash.is_active()) {
+        flash.current_time += time.delta_seconds();
+
+        sprite.color = if flash.is_active() {
+            Color::rgb(1., 0.3, 0.3)
+        } else {
+            Color::WHITE
+        };
+    }
 }
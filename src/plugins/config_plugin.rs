@@ -0,0 +1,116 @@
+use std::fs;
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+
+use crate::component::{
+    enemy::EnemyKind,
+    laser::Laser,
+    push_file::PushFile,
+    velocity::Velocity
+};
+use crate::factory::config_factory::{read_game_config, CONFIG_PATH};
+use crate::resources::{Timer as MonTimer, GameMode, config::GameConfig};
+
+// intervalle entre deux vérifications du fichier de config, en secondes
+const CONFIG_POLL_INTERVAL: f32 = 1.;
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ConfigWatcher::new(CONFIG_PATH))
+            .add_system(watch_config_system);
+    }
+}
+
+pub struct ConfigWatcher {
+    pub path: String,
+    pub last_modified: Option<SystemTime>,
+    pub since_last_check: f32
+}
+
+impl ConfigWatcher {
+    pub fn new(path: &str) -> Self {
+        ConfigWatcher {
+            path: path.to_string(),
+            last_modified: modified_at(path),
+            since_last_check: 0.
+        }
+    }
+}
+
+fn modified_at(path: &str) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[allow(clippy::type_complexity)]
+fn watch_config_system(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    mut watcher: ResMut<ConfigWatcher>,
+    mut config: ResMut<GameConfig>,
+    mut timer: ResMut<MonTimer>,
+    mut velocity_query: Query<(&mut Velocity, Option<&EnemyKind>, Option<&Laser>, Option<&PushFile>)>
+) {
+    // un replay doit garder les réglages enregistrés, le fichier modifié sera relu à la fin
+    if *game_mode == GameMode::Replay {
+        return;
+    }
+
+    watcher.since_last_check += time.delta_seconds();
+    if watcher.since_last_check < CONFIG_POLL_INTERVAL {
+        return;
+    }
+    watcher.since_last_check = 0.;
+
+    let modified = modified_at(&watcher.path);
+    if modified.is_none() || modified == watcher.last_modified {
+        return;
+    }
+    watcher.last_modified = modified;
+
+    let new_config = match read_game_config(&watcher.path) {
+        Ok(new_config) => new_config,
+        Err(e) => {
+            // on garde les réglages en cours tant que le fichier est faux
+            error!("config {} non rechargee, {}", watcher.path, e);
+            return;
+        }
+    };
+
+    let changes = config.changes(&new_config);
+    if changes.is_empty() {
+        return;
+    }
+    for change in changes.iter() {
+        info!("config rechargee : {}", change);
+    }
+    if config.textures != new_config.textures || config.font != new_config.font {
+        warn!("les changements d'assets seront pris en compte au prochain lancement");
+    }
+
+    // le temps deja écoulé de la manche est conservé
+    timer.0 += new_config.round_time - config.round_time;
+
+    // les fichiers deja à l'écran prennent la nouvelle vitesse,
+    // chaque ennemi garde le facteur de difficulté de son apparition
+    let enemy_ratio = new_config.enemy_velocity / config.enemy_velocity;
+    let laser_ratio = new_config.laser_velocity / config.laser_velocity;
+    for (mut velocity, enemy_kind, laser, push_file) in velocity_query.iter_mut() {
+        if enemy_kind.is_some() {
+            velocity.y *= enemy_ratio;
+        } else if laser.is_some() {
+            // chaque arme garde sa direction et sa vitesse relative
+            velocity.x *= laser_ratio;
+            velocity.y *= laser_ratio;
+        } else if push_file.is_some() {
+            velocity.y = -new_config.push_file_velocity;
+        }
+    }
+
+    *config = new_config;
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::{menu::{ActionPrompt, TextControls}, state::InControlsComponent};
+use crate::factory::settings_factory::{create_key_map, save_key_map};
+use crate::resources::{
+    WinSize,
+    GameFonts,
+    gamepad::ActionInput,
+    keymap::{BINDINGS, Action, ControlsScreen, KeyMap, is_bindable, key_name}
+};
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ControlsScreen::default())
+            .add_startup_system(load_key_map_system)
+            .add_system_set(
+                SystemSet::on_enter(AppState::Controls)
+                    .with_system(write_controls_system)
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Controls)
+                    .with_system(controls_input_system)
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Controls)
+                    .with_system(on_exit_controls_system)
+            );
+    }
+}
+
+fn load_key_map_system(mut commands: Commands) {
+    commands.insert_resource(create_key_map());
+}
+
+fn controls_text(key_map: &KeyMap, screen: &ControlsScreen) -> String {
+    BINDINGS
+        .iter()
+        .enumerate()
+        .map(|(i, &(player, action))| {
+            let marker = if i == screen.selected { ">" } else { " " };
+            let key = if i == screen.selected && screen.waiting {
+                "...".to_string()
+            } else {
+                key_map.player_key(player, action).map_or_else(|| "-".to_string(), key_name)
+            };
+            let label = if player == 0 { action.label().to_string() } else { format!("J2 {}", action.label()) };
+            format!("{} {:<12} [{}]", marker, label, key)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn write_controls_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    key_map: Res<KeyMap>,
+    mut screen: ResMut<ControlsScreen>
+) {
+    *screen = ControlsScreen::default();
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: "Commandes".to_string(),
+                    style: TextStyle {
+                        color: Color::RED,
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., win_size.h / 2., 1.),
+            ..Default::default()
+        })
+        .insert(InControlsComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: controls_text(&key_map, &screen),
+                    style: TextStyle {
+                        color: Color::ORANGE,
+                        font: game_fonts.text.clone(),
+                        font_size: 20.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(TextControls)
+        .insert(InControlsComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 20.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 36., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new(
+            "{} changer la touche (puis {} pour annuler), {} touches par defaut, {} revenir au menu",
+            &[Action::Confirm, Action::Back, Action::ResetKeys, Action::Back]
+        ))
+        .insert(InControlsComponent);
+}
+
+fn controls_input_system(
+    mut app_state: ResMut<State<AppState>>,
+    mut actions: ActionInput,
+    mut screen: ResMut<ControlsScreen>,
+    mut query: Query<&mut Text, With<TextControls>>
+) {
+    if screen.waiting && actions.just_pressed(Action::Back) {
+        // le retour annule, sans etre associé à l'action
+        actions.reset(Action::Back);
+        screen.waiting = false;
+    } else if screen.waiting {
+        // la premiere touche utilisable prend la place de l'ancienne
+        let key = match actions.keyboard().get_just_pressed().copied().find(|&key| is_bindable(key)) {
+            Some(key) => key,
+            None => return
+        };
+        let (player, action) = BINDINGS[screen.selected];
+        actions.key_map_mut().bind_player(player, action, key);
+        if let Err(e) = save_key_map(actions.key_map()) {
+            error!("impossible d'enregistrer les touches : {}", e);
+        }
+        // sinon la touche est relue comme une action dans la meme frame
+        actions.reset_key(key);
+        screen.waiting = false;
+    } else if actions.just_pressed(Action::Back) {
+        let _ = app_state.set(AppState::MainMenu);
+        return;
+    } else if actions.just_pressed(Action::Confirm) {
+        screen.waiting = true;
+    } else if actions.just_pressed(Action::ResetKeys) {
+        *actions.key_map_mut() = KeyMap::default();
+        if let Err(e) = save_key_map(actions.key_map()) {
+            error!("impossible d'enregistrer les touches : {}", e);
+        }
+    } else if actions.just_pressed(Action::MoveUp) {
+        screen.selected = (screen.selected + BINDINGS.len() - 1) % BINDINGS.len();
+    } else if actions.just_pressed(Action::MoveDown) {
+        screen.selected = (screen.selected + 1) % BINDINGS.len();
+    } else {
+        return;
+    }
+
+    if let Ok(mut text) = query.get_single_mut() {
+        text.sections[0].value = controls_text(actions.key_map(), &screen);
+    }
+}
+
+fn on_exit_controls_system(
+    mut commands: Commands,
+    query: Query<Entity, With<InControlsComponent>>
+) {
+    query.iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+}
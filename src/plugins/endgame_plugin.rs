@@ -1,10 +1,30 @@
 use bevy::prelude::*;
 
-use crate::{AppState, POLICE};
+use crate::AppState;
 use crate::component::{
-    state::InEndgameComponent
+    state::InEndgameComponent,
+    menu::TextNameEntry
 };
-use crate::resources::{Scoring};
+use crate::factory::{
+    highscore_factory::save_high_scores,
+    replay_factory::{delete_replay, save_replay}
+};
+use crate::plugins::replay_plugin::finished_replay;
+use crate::resources::{
+    Scoring,
+    EndReason,
+    GameMode,
+    GameRng,
+    GameFonts,
+    PlayerCount,
+    highscores::{HighScores, HighScoreEntry, NameEntry},
+    replay::ReplayRecorder,
+    gamepad::{ActionInput, InputDevice},
+    keymap::Action
+};
+use crate::storage::{today, unix_time};
+
+const NAME_MAX_LEN: usize = 12;
 
 pub struct EndgamePlugin;
 
@@ -14,32 +34,149 @@ impl Plugin for EndgamePlugin {
             .add_system_set(
                 SystemSet::on_enter(AppState::EndGame)
                     .with_system(setup_endgame_system)
+                    .with_system(start_name_entry_system)
             )
             .add_system_set(
                 SystemSet::on_update(AppState::EndGame)
                     .with_system(from_endgame_to_menu_system)
+                    .with_system(name_entry_system)
             )
             .add_system_set(
                 SystemSet::on_exit(AppState::EndGame)
                     .with_system(on_exit_endgame_sytem)
-                    .with_system(restart_scoring_system)
             );
     }
 }
 
 fn from_endgame_to_menu_system(
     mut app_state: ResMut<State<AppState>>,
-    kb: Res<Input<KeyCode>>
+    actions: ActionInput
 ) {
-    if kb.just_pressed(KeyCode::Escape) {
-        app_state.set(AppState::MainMenu).unwrap();
+    if actions.just_pressed(Action::Back) {
+        let _ = app_state.set(AppState::MainMenu);
     }
 }
 
-fn restart_scoring_system(
-    mut scoring: ResMut<Scoring>
+fn start_name_entry_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    scoring: Res<Scoring>,
+    game_mode: Res<GameMode>,
+    high_scores: Res<HighScores>,
+    actions: ActionInput,
+    mut name_entry: ResMut<NameEntry>
 ) {
-    scoring.bug_fix = 0;
+    *name_entry = NameEntry::default();
+    // un replay ne rentre pas au classement
+    if *game_mode == GameMode::Replay || !high_scores.qualifies(scoring.points) {
+        return;
+    }
+    name_entry.active = true;
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: name_entry_text(&name_entry, &actions),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 30.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 144., 1.),
+            ..Default::default()
+        })
+        .insert(TextNameEntry)
+        .insert(InEndgameComponent);
+}
+
+fn name_entry_text(name_entry: &NameEntry, actions: &ActionInput) -> String {
+    if name_entry.active {
+        // à la manette le nom ne se tape pas, valider enregistre "anonyme"
+        let validate = match actions.device() {
+            InputDevice::Keyboard => "[Entree]".to_string(),
+            InputDevice::Gamepad => actions.glyph(Action::Confirm)
+        };
+        format!("Nouveau record ! Ton nom : {}_\n{} pour valider", name_entry.name, validate)
+    } else {
+        format!("Score enregistre, bravo {} !", name_entry.name)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn name_entry_system(
+    mut received_characters: EventReader<ReceivedCharacter>,
+    actions: ActionInput,
+    scoring: Res<Scoring>,
+    game_mode: Res<GameMode>,
+    game_rng: Res<GameRng>,
+    recorder: Res<ReplayRecorder>,
+    mut name_entry: ResMut<NameEntry>,
+    mut high_scores: ResMut<HighScores>,
+    mut query: Query<&mut Text, With<TextNameEntry>>
+) {
+    if !name_entry.active {
+        return;
+    }
+
+    for event in received_characters.iter() {
+        let c = event.char;
+        if (c.is_alphanumeric() || c == ' ' || c == '-' || c == '_') && name_entry.name.chars().count() < NAME_MAX_LEN {
+            name_entry.name.push(c);
+        }
+    }
+    if actions.keyboard().just_pressed(KeyCode::Back) {
+        name_entry.name.pop();
+    }
+
+    if actions.keyboard().just_pressed(KeyCode::Return) || actions.pad_just_pressed(Action::Confirm) {
+        let name = name_entry.name.trim().to_string();
+        name_entry.name = if name.is_empty() { "anonyme".to_string() } else { name };
+        name_entry.active = false;
+
+        // les parties du classement gardent leur replay
+        let replay_file = finished_replay(&game_mode, &game_rng, &scoring, &recorder)
+            .and_then(|replay| {
+                let file_name = format!("{}-{}.ron", unix_time(), name_entry.name.replace(' ', "_"));
+                match save_replay(&replay, &file_name) {
+                    Ok(_) => Some(file_name),
+                    Err(e) => {
+                        error!("impossible d'enregistrer le replay ({})", e);
+                        None
+                    }
+                }
+            });
+
+        let dropped = high_scores.insert(HighScoreEntry {
+            name: name_entry.name.clone(),
+            score: scoring.points,
+            date: today(),
+            mode: game_mode.label().to_string(),
+            replay: replay_file
+        });
+        match save_high_scores(&high_scores) {
+            // le replay d'un score sorti du classement n'est plus accessible
+            Ok(_) => {
+                for replay_file in dropped.iter().filter_map(|entry| entry.replay.as_ref()) {
+                    if let Err(e) = delete_replay(replay_file) {
+                        warn!("replay {} non supprime ({})", replay_file, e);
+                    }
+                }
+            }
+            Err(e) => error!("impossible d'enregistrer les scores ({})", e)
+        }
+    }
+
+    if let Ok(mut text) = query.get_single_mut() {
+        text.sections[0].value = name_entry_text(&name_entry, &actions);
+    }
 }
 
 fn on_exit_endgame_sytem(
@@ -52,19 +189,50 @@ fn on_exit_endgame_sytem(
 
 fn setup_endgame_system(
     mut commands: Commands, 
-    asset_server: Res<AssetServer>,
-    scoring: Res<Scoring>
+    game_fonts: Res<GameFonts>,
+    scoring: Res<Scoring>,
+    game_rng: Res<GameRng>,
+    player_count: Res<PlayerCount>,
+    end_reason: Res<EndReason>
 ) {
+    let (reason, color) = match *end_reason {
+        EndReason::Shipped => ("MEP livree sans incident !", Color::GREEN),
+        EndReason::Incidents => ("MEP annulee : trop de bugs en prod", Color::RED),
+        EndReason::Crashed if player_count.is_coop() => ("MEP annulee : les deux pc ont crashe", Color::RED),
+        EndReason::Crashed => ("MEP annulee : le pc a crashe", Color::RED)
+    };
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: reason.to_string(),
+                    style: TextStyle {
+                        color,
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 72., 1.),
+            ..Default::default()
+        })
+        .insert(InEndgameComponent);
 
     // on ajoute le texte du score
     commands
         .spawn_bundle(Text2dBundle {
             text: Text {
                 sections: vec![TextSection {
-                    value: format!("Score : {}", scoring.bug_fix),
+                    value: format!("Score : {} ({} bugs fix)", scoring.points, scoring.bug_fix),
                     style: TextStyle {
                         color: Color::GREEN,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -78,4 +246,54 @@ fn setup_endgame_system(
             ..Default::default()
         })
         .insert(InEndgameComponent);
+    // détail du score
+    let mut breakdown = format!(
+        "points de base : {}\nbonus combo : {}\nbonus score x2 : {}\nbonus serie : {}\nmeilleur combo : {}\nprecision : {:.0}% ({}/{})\n",
+        scoring.base_points,
+        scoring.combo_bonus,
+        scoring.power_up_bonus,
+        scoring.streak_bonus,
+        scoring.best_combo,
+        scoring.accuracy(),
+        scoring.hits,
+        scoring.shots_fired
+    );
+    // en coop, la part de chacun
+    if player_count.is_coop() {
+        for (index, player) in scoring.players.iter().enumerate().take(player_count.0) {
+            breakdown.push_str(&format!(
+                "\nJ{} : {} pts, {} bugs fix, precision {:.0}% ({}/{})",
+                index + 1,
+                player.points,
+                player.bug_fix,
+                player.accuracy(),
+                player.hits,
+                player.shots_fired
+            ));
+        }
+        breakdown.push('\n');
+    }
+    breakdown.push_str(&format!("\nseed : {}", game_rng.seed));
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: breakdown,
+                    style: TextStyle {
+                        color: Color::ORANGE,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Top,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -36., 1.),
+            ..Default::default()
+        })
+        .insert(InEndgameComponent);
 }
@@ -1,60 +1,251 @@
 use bevy::prelude::*;
 use rand::prelude::*;
-use crate::{GameTextures, WinSize, SPRITE_SIZE, ENEMY_MAX};
+use rand::distributions::WeightedIndex;
+use crate::resources::{GameTextures, WinSize};
+use crate::resources::{Lives, PowerUps, Difficulty, EnemySpawnTimer, GameFonts, GameRng, Timer as MonTimer, config::GameConfig, scan::ScanComments, sprint::Sprint};
 use crate::component::{
-    enemy::Enemy,
+    enemy::{Enemy, EnemyKind, ZigZag},
+    health::{Health, HitFlash},
     sprite_size::SpriteSize,
     velocity::Velocity,
-    movable::Movable,
+    movable::{Movable, SimPosition},
     state::InGameComponent
 };
-use crate::AppState;
+use crate::{AppState, TIME_STEP};
+use crate::events::{EnemyDestroyed, EnemyEscaped};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::plugins::player::player_baseline;
+use crate::plugins::scan_plugin::attach_source_label;
+// la difficulté du pas doit etre connue avant de faire apparaitre un ennemi
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct DifficultyLabel;
+
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app
+        .insert_resource(Difficulty::default())
+        .insert_resource(EnemySpawnTimer(0.))
+        .add_event::<EnemyEscaped>()
         .add_system_set(
-            SystemSet::on_update(AppState::InGame)
-                .with_system(enemy_spawn_system_v2)
+            SystemSet::on_enter(AppState::InGame)
+                .with_system(restart_enemy_spawn_system)
+            )
+        .add_system_set_to_stage(
+            FixedUpdateStage,
+            SystemSet::new()
+                .with_run_criteria(run_if_in_game)
+                .after(SimulationLabel::Restore)
+                .with_system(update_difficulty_system.label(DifficultyLabel).after(SimulationLabel::Timer))
+                .with_system(enemy_spawn_system_v2.label(SimulationLabel::Spawn).after(DifficultyLabel))
+                .with_system(enemy_zigzag_system.before(SimulationLabel::Movement))
+                .with_system(enemy_escape_system.after(SimulationLabel::Movement).before(SimulationLabel::Collision))
+                .with_system(split_merge_conflict_system.after(SimulationLabel::PickUp))
             );
     }
 }
 
+fn restart_enemy_spawn_system(
+    mut spawn_timer: ResMut<EnemySpawnTimer>
+) {
+    spawn_timer.0 = 0.;
+}
+
+fn update_difficulty_system(
+    timer: Res<MonTimer>,
+    config: Res<GameConfig>,
+    mut difficulty: ResMut<Difficulty>
+) {
+    // plus la MEP approche, plus c'est la panique
+    let progress = (1. - timer.0 / config.round_time).clamp(0., 1.);
+    let step = config.difficulty_at(progress);
+
+    difficulty.enemy_max = ((config.enemy_max as f32 * step.enemy_max_factor).round() as u32).max(1);
+    difficulty.speed_factor = step.speed_factor;
+    difficulty.spawn_interval = step.spawn_interval;
+}
+
+#[allow(clippy::too_many_arguments)]
 fn enemy_spawn_system_v2(
     mut commands: Commands,
     game_textures: Res<GameTextures>,
     win_size: Res<WinSize>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>,
+    mut spawn_timer: ResMut<EnemySpawnTimer>,
+    mut game_rng: ResMut<GameRng>,
+    game_fonts: Res<GameFonts>,
+    scan_comments: Option<ResMut<ScanComments>>,
+    sprint: Option<ResMut<Sprint>>,
     query: Query<Entity, With<Enemy>>
 ) {
+    spawn_timer.0 += TIME_STEP;
+
     let enemies = query.iter().collect::<Vec<Entity>>();
-    if (enemies.len() as u32) < ENEMY_MAX {
-        spawn_enemy(&mut commands, &game_textures, &win_size);
+    if (enemies.len() as u32) < difficulty.enemy_max && spawn_timer.0 >= difficulty.spawn_interval {
+        spawn_timer.0 = 0.;
+
+        // en mode sprint, les vagues suivent les commits du dépot
+        if let Some(mut sprint) = sprint {
+            if let Some(index) = sprint.next_enemy() {
+                let commit = &sprint.commits[index];
+                let (kind, label) = (commit.enemy_kind(), format!("{} {}", commit.hash, commit.author));
+
+                let enemy = spawn_enemy(&mut commands, &mut game_rng.rng, &game_textures, &win_size, &config, &difficulty, Some(kind));
+                attach_enemy_label(&mut commands, enemy, label, &game_fonts, &config);
+                sprint.alive.insert(enemy, index);
+            }
+            return;
+        }
+
+        let enemy = spawn_enemy(&mut commands, &mut game_rng.rng, &game_textures, &win_size, &config, &difficulty, None);
+
+        // en mode scan, chaque ennemi porte un vrai commentaire tant qu'il en reste
+        if let Some(mut scan_comments) = scan_comments {
+            if let Some(index) = scan_comments.take(game_rng.seed) {
+                attach_source_label(&mut commands, enemy, index, &scan_comments, &game_fonts, &config);
+            }
+        }
+    }
+}
+
+fn enemy_escape_system(
+    mut commands: Commands,
+    mut lives: ResMut<Lives>,
+    power_ups: Res<PowerUps>,
+    mut enemy_escaped: EventWriter<EnemyEscaped>,
+    win_size: Res<WinSize>,
+    config: Res<GameConfig>,
+    enemy_query: Query<(Entity, &Transform, &EnemyKind), With<Enemy>>
+) {
+    // un fichier fixme qui passe sous la ligne de départ du pc devient un bug en prod,
+    // meme si le joueur est monté plus haut
+    let baseline = player_baseline(&win_size, &config);
+    for (entity, enemy_tf, kind) in enemy_query.iter() {
+        if enemy_tf.translation.y < baseline {
+            commands.entity(entity).despawn_recursive();
+            // le bouclier encaisse le bug à la place de la prod
+            if power_ups.shield == 0 {
+                lives.0 = lives.0.saturating_sub(1);
+            }
+            enemy_escaped.send(EnemyEscaped { entity, kind: *kind });
+        }
+    }
+}
+
+fn enemy_zigzag_system(
+    mut query: Query<(&mut Velocity, &mut ZigZag), With<Enemy>>
+) {
+    for (mut velocity, mut zigzag) in query.iter_mut() {
+        zigzag.elapsed += TIME_STEP;
+        velocity.x = zigzag.amplitude * (zigzag.elapsed * zigzag.frequency).cos();
+    }
+}
+
+fn split_merge_conflict_system(
+    mut commands: Commands,
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    game_textures: Res<GameTextures>,
+    config: Res<GameConfig>,
+    difficulty: Res<Difficulty>
+) {
+    // le conflit se coupe en deux moitiés qui continuent de tomber, à la vitesse du moment
+    let offset = config.sprite_size.0 / 2.;
+    for event in enemy_destroyed.iter().filter(|event| event.kind == EnemyKind::MergeConflict && !event.cleared) {
+        for side in [-1., 1.] {
+            let translation = event.position + Vec3::new(side * offset, 0., 0.);
+            spawn_enemy_kind(&mut commands, &game_textures, &config, EnemyKind::ConflictHalf, translation, difficulty.speed_factor);
+        }
     }
 }
 
 fn spawn_enemy(
     commands: &mut Commands,
+    rng: &mut impl Rng,
     game_textures: &Res<GameTextures>,
-    win_size: &Res<WinSize>
-) {
-    let mut rng = thread_rng();
+    win_size: &Res<WinSize>,
+    config: &Res<GameConfig>,
+    difficulty: &Res<Difficulty>,
+    kind: Option<EnemyKind>
+) -> Entity {
     let w_span = win_size.w / 2. - 100.;
     let x = rng.gen_range(-w_span..w_span);
     let y = win_size.h / 2. + 64.;// rng.gen_range(-h_span..h_span);
 
-    commands
-        .spawn_bundle(SpriteBundle {
-            transform: Transform {
-                translation: Vec3::new(x, y, 10.),
-                ..Default::default()
-            },
-            texture: game_textures.fixme_file.clone(),
+    // tirage pondéré du type de fichier (la config est validée, au moins un poids non nul)
+    let kind = kind.unwrap_or_else(|| {
+        let weights = WeightedIndex::new(config.enemy_weights.iter().map(|entry| entry.weight)).unwrap();
+        config.enemy_weights[weights.sample(rng)].kind
+    });
+
+    spawn_enemy_kind(commands, game_textures, config, kind, Vec3::new(x, y, 10.), difficulty.speed_factor)
+}
+
+pub fn spawn_enemy_kind(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    config: &GameConfig,
+    kind: EnemyKind,
+    translation: Vec3,
+    speed_factor: f32
+) -> Entity {
+    let scale = kind.scale();
+    let mut enemy = commands.spawn_bundle(SpriteBundle {
+        transform: Transform {
+            translation,
+            scale: Vec3::new(scale, scale, 1.),
             ..Default::default()
-        })
-        .insert(SpriteSize::from(SPRITE_SIZE))
-        .insert(Velocity {x: 0., y: -0.5})
+        },
+        texture: game_textures.enemy(kind),
+        ..Default::default()
+    });
+
+    enemy
+        .insert(SpriteSize::from(config.sprite_size))
+        .insert(Velocity {x: 0., y: -config.enemy_velocity * speed_factor * kind.speed_factor()})
         .insert(Movable { auto_despawn: true })
+        .insert(SimPosition::at(translation))
+        .insert(Health::new(kind.hit_points()))
+        .insert(HitFlash::default())
+        .insert(kind)
         .insert(Enemy)
         .insert(InGameComponent);
-}
\ No newline at end of file
+
+    if kind == EnemyKind::FlakyTest {
+        enemy.insert(ZigZag { amplitude: 0.6, frequency: 4., elapsed: 0. });
+    }
+
+    enemy.id()
+}
+
+/// Texte affiché sous le sprite de l'ennemi, despawn avec lui
+pub fn attach_enemy_label(
+    commands: &mut Commands,
+    enemy: Entity,
+    label: String,
+    game_fonts: &GameFonts,
+    config: &GameConfig
+) {
+    commands.entity(enemy).with_children(|parent| {
+        parent.spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: label,
+                    style: TextStyle {
+                        color: Color::WHITE,
+                        font: game_fonts.text.clone(),
+                        font_size: 14.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Top,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -config.sprite_size.1 / 2., 1.),
+            ..Default::default()
+        });
+    });
+}
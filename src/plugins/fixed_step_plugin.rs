@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::ShouldRun;
+use bevy::time::{FixedTimestep, FixedTimesteps};
+use bevy::transform::TransformSystem;
+
+use crate::{AppState, TIME_STEP};
+use crate::component::{health::Health, movable::SimPosition, player::Player};
+use crate::resources::{Lives, Timer as MonTimer};
+
+pub const FIXED_TIMESTEP_LABEL: &str = "gameplay_fixed_timestep";
+
+/// Stage de simulation, exécuté autant de fois que nécessaire pour rattraper
+/// le temps réel par pas de TIME_STEP, quel que soit le framerate
+#[derive(StageLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedUpdateStage;
+
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationLabel {
+    Restore,
+    /// commandes du joueur du pas en cours (lecture d'un replay)
+    Input,
+    /// enregistrement des commandes, avant qu'elles soient consommées
+    Record,
+    /// décompte du temps de la manche, lu par la difficulté
+    Timer,
+    /// apparition des ennemis, premier tirage du pas dans GameRng
+    Spawn,
+    Movement,
+    Collision,
+    /// ramassage des fichiers poussés, qui peut aussi détruire des ennemis
+    PickUp
+}
+
+pub struct FixedStepPlugin {
+    /// un pas de simulation par `App::update`, sans attendre le temps réel
+    /// (simulation sans fenetre, où rien n'est affiché)
+    pub step_every_update: bool
+}
+
+impl Default for FixedStepPlugin {
+    fn default() -> Self {
+        FixedStepPlugin { step_every_update: false }
+    }
+}
+
+impl Plugin for FixedStepPlugin {
+    fn build(&self, app: &mut App) {
+        if self.step_every_update {
+            app.add_stage_after(CoreStage::Update, FixedUpdateStage, SystemStage::parallel());
+        } else {
+            app
+                .add_stage_after(
+                    CoreStage::Update,
+                    FixedUpdateStage,
+                    SystemStage::parallel()
+                        .with_run_criteria(FixedTimestep::step(TIME_STEP as f64).with_label(FIXED_TIMESTEP_LABEL))
+                )
+                .add_system_set_to_stage(
+                    CoreStage::PostUpdate,
+                    SystemSet::new()
+                        .with_run_criteria(run_if_in_game)
+                        .with_system(interpolate_sim_position_system.before(TransformSystem::TransformPropagate))
+                );
+        }
+
+        app.add_system_set_to_stage(
+            FixedUpdateStage,
+            SystemSet::new()
+                .with_run_criteria(run_if_in_game)
+                .with_system(restore_sim_position_system.label(SimulationLabel::Restore))
+        );
+    }
+}
+
+/// Équivalent de `SystemSet::on_update(AppState::InGame)` utilisable hors du stage
+/// qui pilote les états (on_update boucle indéfiniment dans un autre stage).
+/// Une manche terminée n'est plus simulée en attendant le passage à l'écran de fin,
+/// sinon le nombre de pas joués dépendrait du framerate et un replay divergerait
+pub fn run_if_in_game(
+    state: Res<State<AppState>>,
+    timer: Res<MonTimer>,
+    lives: Res<Lives>,
+    player_query: Query<&Health, With<Player>>
+) -> ShouldRun {
+    let crashed = all_players_crashed(player_query.iter());
+    if *state.current() == AppState::InGame && timer.0 >= 0. && lives.0 > 0 && !crashed {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Vrai quand tous les pc sont à zéro d'intégrité : seul, le premier crash termine la manche,
+/// en coop l'autre joueur continue
+pub fn all_players_crashed<'a>(players: impl Iterator<Item = &'a Health>) -> bool {
+    let mut players = players.peekable();
+    players.peek().is_some() && players.all(|health| health.current == 0)
+}
+
+// le Transform a été interpolé pour l'affichage, la simulation repart de la position exacte
+fn restore_sim_position_system(
+    mut query: Query<(&mut Transform, &SimPosition)>
+) {
+    for (mut transform, sim_position) in query.iter_mut() {
+        transform.translation = sim_position.current;
+    }
+}
+
+fn interpolate_sim_position_system(
+    fixed_timesteps: Res<FixedTimesteps>,
+    mut query: Query<(&mut Transform, &SimPosition)>
+) {
+    let alpha = fixed_timesteps
+        .get(FIXED_TIMESTEP_LABEL)
+        .map_or(1., |state| state.overstep_percentage() as f32);
+
+    for (mut transform, sim_position) in query.iter_mut() {
+        transform.translation = sim_position.previous.lerp(sim_position.current, alpha);
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::menu::ActionPrompt;
+use crate::resources::{
+    gamepad::{Controllers, InputDevice, prompt_text, stick_direction},
+    keymap::KeyMap
+};
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Controllers::default())
+            .add_system(hot_plug_system)
+            .add_system(last_device_system)
+            // apres les écrans, qui créent leurs textes pendant l'update
+            .add_system_to_stage(CoreStage::PostUpdate, update_prompts_system);
+    }
+}
+
+fn hot_plug_system(
+    mut gamepad_events: EventReader<GamepadEvent>,
+    gamepads: Res<Gamepads>,
+    mut controllers: ResMut<Controllers>,
+    mut app_state: ResMut<State<AppState>>
+) {
+    for GamepadEvent(gamepad, event_type) in gamepad_events.iter() {
+        match event_type {
+            GamepadEventType::Connected => {
+                info!("manette {} branchee", gamepad.0);
+                if controllers.gamepad.is_none() {
+                    controllers.gamepad = Some(*gamepad);
+                }
+            }
+            GamepadEventType::Disconnected if controllers.gamepad == Some(*gamepad) => {
+                info!("manette {} debranchee", gamepad.0);
+                controllers.gamepad = gamepads.iter().find(|other| *other != gamepad).copied();
+                if controllers.gamepad.is_none() {
+                    controllers.device = InputDevice::Keyboard;
+                }
+
+                // sinon le pc continue sans commande
+                if *app_state.current() == AppState::InGame {
+                    let _ = app_state.push(AppState::Paused);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn last_device_system(
+    kb: Res<Input<KeyCode>>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut controllers: ResMut<Controllers>
+) {
+    let gamepad_used = controllers.gamepad.map_or(false, |gamepad| {
+        buttons.get_just_pressed().any(|button| button.0 == gamepad)
+            || stick_direction(&axes, gamepad) != Vec2::ZERO
+    });
+
+    let device = if kb.get_just_pressed().next().is_some() {
+        InputDevice::Keyboard
+    } else if gamepad_used {
+        InputDevice::Gamepad
+    } else {
+        return;
+    };
+
+    // seulement s'il change, les textes sont réécrits à chaque changement
+    if controllers.device != device {
+        controllers.device = device;
+    }
+}
+
+fn update_prompts_system(
+    controllers: Res<Controllers>,
+    key_map: Res<KeyMap>,
+    mut query: Query<(&mut Text, &ActionPrompt, ChangeTrackers<ActionPrompt>)>
+) {
+    let refresh_all = controllers.is_changed() || key_map.is_changed();
+
+    for (mut text, prompt, tracker) in query.iter_mut() {
+        if refresh_all || tracker.is_added() {
+            if let Some(section) = text.sections.last_mut() {
+                section.value = prompt_text(prompt.template, &prompt.actions, &key_map, controllers.device);
+            }
+        }
+    }
+}
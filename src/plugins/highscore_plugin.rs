@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::{menu::ActionPrompt, state::InHighScoresComponent};
+use crate::factory::highscore_factory::create_high_scores;
+use crate::resources::{WinSize, GameFonts, highscores::{HighScores, NameEntry}, gamepad::ActionInput, keymap::Action};
+
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(NameEntry::default())
+            .add_startup_system(load_high_scores_system)
+            .add_system_set(
+                SystemSet::on_enter(AppState::HighScores)
+                    .with_system(write_high_scores_system)
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::HighScores)
+                    .with_system(from_high_scores_to_menu_system)
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::HighScores)
+                    .with_system(on_exit_high_scores_system)
+            );
+    }
+}
+
+fn load_high_scores_system(mut commands: Commands) {
+    commands.insert_resource(create_high_scores());
+}
+
+fn write_high_scores_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    high_scores: Res<HighScores>
+) {
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: "Meilleurs scores".to_string(),
+                    style: TextStyle {
+                        color: Color::RED,
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., win_size.h / 2., 1.),
+            ..Default::default()
+        })
+        .insert(InHighScoresComponent);
+
+    let table = if high_scores.entries.is_empty() {
+        "aucun score pour l'instant".to_string()
+    } else {
+        high_scores.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| format!("{:>2}. {:<12} {:>6}  {}  {}", i + 1, entry.name, entry.score, entry.date, entry.mode))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: table,
+                    style: TextStyle {
+                        color: Color::ORANGE,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(InHighScoresComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 36., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("Press {} pour revenir au menu", &[Action::Back]))
+        .insert(InHighScoresComponent);
+}
+
+fn from_high_scores_to_menu_system(
+    mut app_state: ResMut<State<AppState>>,
+    actions: ActionInput
+) {
+    if actions.just_pressed(Action::Back) {
+        let _ = app_state.set(AppState::MainMenu);
+    }
+}
+
+fn on_exit_high_scores_system(
+    mut commands: Commands,
+    query: Query<Entity, With<InHighScoresComponent>>
+) {
+    query.iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+}
@@ -1,15 +1,34 @@
 use bevy::prelude::*;
 
-use crate::{AppState, TIME, TIME_STEP, BASE_SPEED, POLICE};
+use crate::{AppState, TIME_STEP};
 use crate::component::{
-    hud::TextTimer,
+    hud::{TextTimer, TextLives, TextPlayerScore},
     state::InGameComponent,
     velocity::Velocity,
-    movable::Movable,
-    player::ScoreBugFix,
-    temporary::Temporary
+    movable::{Movable, SimPosition},
+    player::{Player, ScoreBugFix},
+    health::Health,
+    temporary::Temporary,
+    laser::{Laser, Piercing}
+};
+use crate::events::LaserMissed;
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, all_players_crashed, run_if_in_game};
+use crate::plugins::player::hud_column_x;
+use crate::plugins::scoring_plugin::player_score_text;
+use crate::options::LaunchOptions;
+use crate::resources::{
+    WinSize,
+    Timer as MonTimer,
+    GameTextures,
+    GameFonts,
+    GameRng,
+    Lives,
+    EndReason,
+    PlayerCount,
+    PlayerScore,
+    config::GameConfig,
+    replay::ReplayPlayback
 };
-use crate::resources::{WinSize, Timer as MonTimer, GameTextures};
 
 pub struct IngamePlugin;
 
@@ -21,7 +40,10 @@ impl Plugin for IngamePlugin {
                     .with_system(print_bg)
                     .with_system(write_scoring_system)
                     .with_system(write_timer_system)
+                    .with_system(write_lives_system)
                     .with_system(restart_timer_system)
+                    .with_system(restart_lives_system)
+                    .with_system(restart_rng_system)
             )
             .add_system_set(
                 SystemSet::on_exit(AppState::InGame)
@@ -30,9 +52,16 @@ impl Plugin for IngamePlugin {
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
                     .with_system(from_game_to_endgame_system)
-                    .with_system(movable_system)
-                    .with_system(update_timer_system)
                     .with_system(update_timer_text_system)
+                    .with_system(update_lives_text_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Restore)
+                    .with_system(movable_system.label(SimulationLabel::Movement))
+                    .with_system(update_timer_system.label(SimulationLabel::Timer))
                     .with_system(update_temporary_entity_system)
             );
     }
@@ -56,9 +85,10 @@ fn print_bg(
 }
 
 fn write_scoring_system(
-    asset_server: Res<AssetServer>,
+    game_fonts: Res<GameFonts>,
     mut commands: Commands, 
     win_size: Res<WinSize>,
+    player_count: Res<PlayerCount>
 ) {
     let pos_score = (-win_size.w / 2., win_size.h / 2.);
 
@@ -67,10 +97,10 @@ fn write_scoring_system(
         .spawn_bundle(Text2dBundle {
             text: Text {
                 sections: vec![TextSection {
-                    value: "bug fix : 0".to_string(),
+                    value: "bug fix : 0 (0 pts) x1".to_string(),
                     style: TextStyle {
                         color: Color::ORANGE,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -87,11 +117,37 @@ fn write_scoring_system(
         })
         .insert(ScoreBugFix)
         .insert(InGameComponent);
+
+    if !player_count.is_coop() {
+        return;
+    }
+
+    // en coop, la part de chaque joueur sous son intégrité
+    for index in 0..player_count.0 {
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: player_score_text(index, &PlayerScore::default()),
+                        style: TextStyle {
+                            color: Color::ORANGE,
+                            font: game_fonts.text.clone(),
+                            font_size: 24.
+                        }
+                    }],
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(hud_column_x(&win_size, index), win_size.h / 2. - 176., 2.),
+                ..Default::default()
+            })
+            .insert(TextPlayerScore(index))
+            .insert(InGameComponent);
+    }
 }
 
 fn write_timer_system(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    game_fonts: Res<GameFonts>,
     win_size: Res<WinSize>
 ) {
     let x = 0.;
@@ -104,7 +160,7 @@ fn write_timer_system(
                     value: "MEP in 0 seconds".to_string(),
                     style: TextStyle {
                         color: Color::RED,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -124,8 +180,56 @@ fn write_timer_system(
         .insert(InGameComponent);
 }
 
-fn restart_timer_system(mut timer: ResMut<MonTimer>) {
-    timer.0 = TIME;
+fn write_lives_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    config: Res<GameConfig>
+) {
+    // sous le score des bugs fix
+    let pos_lives = (-win_size.w / 2., win_size.h / 2. - 36.);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: format!("prod lives : {}", config.lives),
+                    style: TextStyle {
+                        color: Color::PINK,
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(pos_lives.0, pos_lives.1, 2.),
+            ..Default::default()
+        })
+        .insert(TextLives)
+        .insert(InGameComponent);
+}
+
+fn restart_timer_system(mut timer: ResMut<MonTimer>, config: Res<GameConfig>) {
+    timer.0 = config.round_time;
+}
+
+fn restart_lives_system(mut lives: ResMut<Lives>, config: Res<GameConfig>) {
+    lives.0 = config.lives;
+}
+
+fn restart_rng_system(
+    mut game_rng: ResMut<GameRng>,
+    options: Res<LaunchOptions>,
+    config: Res<GameConfig>,
+    replay_playback: Res<ReplayPlayback>
+) {
+    let seed = replay_playback.current.as_ref()
+        .map(|playback| playback.replay.seed)
+        .or(options.seed)
+        .or(config.seed)
+        .unwrap_or_else(rand::random);
+    game_rng.reseed(seed);
+    info!("nouvelle manche, seed : {}", seed);
 }
 
 fn on_exit_ingame_sytem(
@@ -133,27 +237,49 @@ fn on_exit_ingame_sytem(
     query: Query<Entity, With<InGameComponent>>
 ) {
     query.iter()
-        .for_each(|entity| commands.entity(entity).despawn());
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
 }
 
 fn from_game_to_endgame_system(
     mut app_state: ResMut<State<AppState>>,
-    timer: Res<MonTimer>
+    mut end_reason: ResMut<EndReason>,
+    timer: Res<MonTimer>,
+    lives: Res<Lives>,
+    player_query: Query<&Health, With<Player>>
 ) {
-    if timer.0 < 0. {
-        app_state.set(AppState::EndGame).unwrap();
+    let reason = if all_players_crashed(player_query.iter()) {
+        EndReason::Crashed
+    } else if lives.0 == 0 {
+        EndReason::Incidents
+    } else if timer.0 < 0. {
+        EndReason::Shipped
+    } else {
+        return;
+    };
+
+    // la transition peut etre refusée si une pause est demandée dans la meme frame,
+    // elle sera retentée à la reprise
+    if app_state.set(AppState::EndGame).is_ok() {
+        *end_reason = reason;
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn movable_system(
     mut commands: Commands,
     win_size: Res<WinSize>,
-    mut query: Query<(Entity, &Velocity, &mut Transform, &Movable)>
+    config: Res<GameConfig>,
+    mut laser_missed: EventWriter<LaserMissed>,
+    mut query: Query<(Entity, &Velocity, &mut Transform, &mut SimPosition, &Movable, Option<&Laser>, Option<&Piercing>)>
 ) {
-    for (entity, velocity, mut transform, movable) in query.iter_mut() {
-        let translation = &mut transform.translation;
-        translation.x += velocity.x * TIME_STEP * BASE_SPEED;
-        translation.y += velocity.y * TIME_STEP * BASE_SPEED;
+    for (entity, velocity, mut transform, mut sim_position, movable, laser, piercing) in query.iter_mut() {
+        // garde les deux derniers pas pour interpoler l'affichage
+        sim_position.previous = sim_position.current;
+        sim_position.current.x += velocity.x * TIME_STEP * config.base_speed;
+        sim_position.current.y += velocity.y * TIME_STEP * config.base_speed;
+        transform.translation = sim_position.current;
+
+        let translation = &transform.translation;
 
         if movable.auto_despawn {
             const MARGIN: f32 = 300.;
@@ -161,17 +287,22 @@ fn movable_system(
                 || translation.y < -win_size.h / 2. - MARGIN
                 || translation.x > win_size.w / 2. + MARGIN
                 || translation.x < -win_size.w / 2. - MARGIN {
-                commands.entity(entity).despawn();
+                commands.entity(entity).despawn_recursive();
+
+                // un hotfix qui a touché au moins un ennemi n'est pas raté
+                let has_hit = piercing.map_or(false, |piercing| !piercing.hit.is_empty());
+                if laser.is_some() && !has_hit {
+                    laser_missed.send(LaserMissed);
+                }
             }
         }
     }
 }
 
 fn update_timer_system(
-    mut timer: ResMut<MonTimer>,
-    time: Res<Time>
+    mut timer: ResMut<MonTimer>
 ) {
-    timer.0 -= time.delta_seconds();
+    timer.0 -= TIME_STEP;
 }
 
 fn update_timer_text_system(
@@ -187,14 +318,22 @@ fn update_timer_text_system(
     text.sections[0].value = format!("MEP in {:.0} seconds", time_val).to_string();
 }
 
+fn update_lives_text_system(
+    lives: Res<Lives>,
+    mut query_lives_text: Query<&mut Text, With<TextLives>>
+) {
+    if let Ok(mut text) = query_lives_text.get_single_mut() {
+        text.sections[0].value = format!("prod lives : {}", lives.0);
+    }
+}
+
 fn update_temporary_entity_system(
     mut commands: Commands,
-    time: Res<Time>,
     mut query: Query<(Entity, &mut Temporary)>
 ) {
     query.iter_mut()
         .for_each(|(entity, mut temporary)| {
-            temporary.current_time += time.delta_seconds();
+            temporary.current_time += TIME_STEP;
 
             if temporary.current_time > temporary.duration {
                 commands.entity(entity).despawn();
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{AppState, POLICE};
+use crate::AppState;
 use crate::component::{
+    menu::{ActionPrompt, TextPlayers},
     state::InMenuComponent
 };
-use crate::resources::{WinSize};
+use crate::resources::{WinSize, GameFonts, MAX_PLAYERS, PlayerCount, gamepad::ActionInput, keymap::Action};
 
 pub struct MenuPlugin;
 
@@ -21,15 +22,23 @@ impl Plugin for MenuPlugin {
             )
             .add_system_set(
                 SystemSet::on_update(AppState::MainMenu)
-                    .with_system(from_menu_to_game_system)
+                    .with_system(from_menu_system)
+                    .with_system(toggle_coop_system)
             );
     }
 }
 
+// le mode est dans la première section, la dernière est réécrite avec la touche
+fn players_text(player_count: &PlayerCount) -> String {
+    let mode = if player_count.is_coop() { "coop a deux" } else { "solo" };
+    format!("Mode {} : ", mode)
+}
+
 fn write_menu_system(
-    asset_server: Res<AssetServer>,
+    game_fonts: Res<GameFonts>,
     mut commands: Commands,
-    win_size: Res<WinSize>
+    win_size: Res<WinSize>,
+    player_count: Res<PlayerCount>
 ) {
     // on ajoute le texte du score
 
@@ -41,7 +50,7 @@ fn write_menu_system(
                     value: "Foyer MEP horror".to_string(),
                     style: TextStyle {
                         color: Color::RED,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -60,10 +69,10 @@ fn write_menu_system(
         .spawn_bundle(Text2dBundle {
             text: Text {
                 sections: vec![TextSection {
-                    value: "Press [X] pour fix un bug lorsqu'un\nfichier \"fixme\" apparait".to_string(),
+                    value: String::new(),
                     style: TextStyle {
                         color: Color::ORANGE,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -78,16 +87,17 @@ fn write_menu_system(
             // transform: Transform::from_xyz(pos_score.0, pos_score.1, 1.),
             ..Default::default()
         })
+        .insert(ActionPrompt::new("Press {} pour fix un bug lorsqu'un\nfichier \"fixme\" apparait", &[Action::Fire]))
         .insert(InMenuComponent);
 
     commands
         .spawn_bundle(Text2dBundle {
             text: Text {
                 sections: vec![TextSection {
-                    value: "Press [Espace] pour jouer".to_string(),
+                    value: String::new(),
                     style: TextStyle {
                         color: Color::YELLOW,
-                        font: asset_server.load(POLICE),
+                        font: game_fonts.text.clone(),
                         font_size: 36.
                     }
                 }],
@@ -103,6 +113,110 @@ fn write_menu_system(
             transform: Transform::from_xyz(0., -win_size.h / 2. + 36., 1.),
             ..Default::default()
         })
+        .insert(ActionPrompt::new("Press {} pour jouer", &[Action::Confirm]))
+        .insert(InMenuComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 72., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("Press {} pour les meilleurs scores", &[Action::HighScores]))
+        .insert(InMenuComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 100., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("Press {} pour revoir une partie", &[Action::Replays]))
+        .insert(InMenuComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 128., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("Press {} pour changer les commandes", &[Action::Controls]))
+        .insert(InMenuComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![
+                    TextSection {
+                        value: players_text(&player_count),
+                        style: TextStyle {
+                            color: Color::YELLOW,
+                            font: game_fonts.text.clone(),
+                            font_size: 24.
+                        }
+                    },
+                    TextSection {
+                        value: String::new(),
+                        style: TextStyle {
+                            color: Color::YELLOW,
+                            font: game_fonts.text.clone(),
+                            font_size: 24.
+                        }
+                    }
+                ],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 156., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("Press {} pour changer", &[Action::TogglePlayers]))
+        .insert(TextPlayers)
         .insert(InMenuComponent);
 }
 
@@ -115,11 +229,35 @@ fn on_exit_menu_sytem(
         .for_each(|entity| commands.entity(entity).despawn());
 }
 
-fn from_menu_to_game_system(
+// une seule transition par frame : un deuxième `set` dans la meme frame serait refusé
+fn from_menu_system(
     mut app_state: ResMut<State<AppState>>,
-    kb: Res<Input<KeyCode>>
+    actions: ActionInput
 ) {
-    if kb.just_pressed(KeyCode::Space) {
-        app_state.set(AppState::InGame).unwrap();
+    let next = if actions.just_pressed(Action::Confirm) {
+        AppState::InGame
+    } else if actions.just_pressed(Action::HighScores) {
+        AppState::HighScores
+    } else if actions.just_pressed(Action::Replays) {
+        AppState::Replay
+    } else if actions.just_pressed(Action::Controls) {
+        AppState::Controls
+    } else {
+        return;
+    };
+    let _ = app_state.set(next);
+}
+
+fn toggle_coop_system(
+    actions: ActionInput,
+    mut player_count: ResMut<PlayerCount>,
+    mut query: Query<&mut Text, With<TextPlayers>>
+) {
+    if actions.just_pressed(Action::TogglePlayers) {
+        player_count.0 = if player_count.is_coop() { 1 } else { MAX_PLAYERS };
+
+        if let Ok(mut text) = query.get_single_mut() {
+            text.sections[0].value = players_text(&player_count);
+        }
     }
 }
@@ -3,4 +3,15 @@ pub mod enemy;
 pub mod collide_plugin;
 pub mod endgame_plugin;
 pub mod menu_plugin;
-pub mod ingame_plugin;
\ No newline at end of file
+pub mod ingame_plugin;
+pub mod config_plugin;
+pub mod scoring_plugin;
+pub mod highscore_plugin;
+pub mod pause_plugin;
+pub mod fixed_step_plugin;
+pub mod replay_plugin;
+pub mod scan_plugin;
+pub mod sprint_plugin;
+pub mod power_up_plugin;
+pub mod controls_plugin;
+pub mod gamepad_plugin;
\ No newline at end of file
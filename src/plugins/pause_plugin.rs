@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::{menu::ActionPrompt, state::InPauseComponent};
+use crate::resources::{WinSize, GameFonts, gamepad::ActionInput, keymap::Action};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Paused est empilé sur InGame : les entités de la partie restent en place
+            // et les systèmes de InGame ne tournent plus tant que la pause est active
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(from_game_to_pause_system)
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Paused)
+                    .with_system(write_pause_system)
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Paused)
+                    .with_system(pause_menu_system)
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Paused)
+                    .with_system(on_exit_pause_system)
+            );
+    }
+}
+
+// la touche retour met aussi en pause
+const PAUSE_ACTIONS: [Action; 2] = [Action::Pause, Action::Back];
+
+fn is_pause_key_pressed(actions: &ActionInput) -> bool {
+    PAUSE_ACTIONS.iter().any(|&action| actions.just_pressed(action))
+}
+
+fn reset_pause_keys(actions: &mut ActionInput) {
+    for action in PAUSE_ACTIONS {
+        actions.reset(action);
+    }
+}
+
+fn from_game_to_pause_system(
+    mut app_state: ResMut<State<AppState>>,
+    mut actions: ActionInput
+) {
+    if is_pause_key_pressed(&actions) && app_state.push(AppState::Paused).is_ok() {
+        // sinon la pause relit la touche dans la meme frame et se referme aussitot
+        reset_pause_keys(&mut actions);
+    }
+}
+
+fn pause_menu_system(
+    mut app_state: ResMut<State<AppState>>,
+    mut actions: ActionInput
+) {
+    if is_pause_key_pressed(&actions) {
+        if app_state.pop().is_ok() {
+            reset_pause_keys(&mut actions);
+        }
+    } else if actions.just_pressed(Action::Restart) {
+        // replace vide toute la pile : InGame est quitté puis relancé
+        if app_state.replace(AppState::InGame).is_ok() {
+            actions.reset(Action::Restart);
+        }
+    } else if actions.just_pressed(Action::Quit) {
+        // le bouton Select ouvrirait aussi les commandes depuis le menu dans la meme frame
+        if app_state.replace(AppState::MainMenu).is_ok() {
+            actions.reset(Action::Quit);
+        }
+    }
+}
+
+fn write_pause_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0., 0., 0., 0.7),
+                custom_size: Some(Vec2::new(win_size.w, win_size.h)),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 0., 50.),
+            ..Default::default()
+        })
+        .insert(InPauseComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![
+                    TextSection {
+                        value: "Pause\n\n".to_string(),
+                        style: TextStyle {
+                            color: Color::RED,
+                            font: game_fonts.text.clone(),
+                            font_size: 48.
+                        }
+                    },
+                    TextSection {
+                        value: String::new(),
+                        style: TextStyle {
+                            color: Color::YELLOW,
+                            font: game_fonts.text.clone(),
+                            font_size: 30.
+                        }
+                    }
+                ],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., 0., 51.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("{} reprendre\n{} recommencer\n{} quitter vers le menu", &[Action::Pause, Action::Restart, Action::Quit]))
+        .insert(InPauseComponent);
+}
+
+fn on_exit_pause_system(
+    mut commands: Commands,
+    query: Query<Entity, With<InPauseComponent>>
+) {
+    query.iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+}
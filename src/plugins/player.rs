@@ -1,17 +1,54 @@
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
 
-use crate::{SPRITE_SIZE, SPRITE_SCALE};
-use crate::resources::{GameTextures, WinSize};
+use crate::resources::{GameTextures, GameFonts, GameMode, PlayerCount, PowerUps, WinSize, config::GameConfig, gamepad::ActionInput, keymap::Action};
 use crate::AppState;
+use crate::events::ShotFired;
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
 use crate::component::{
-    player::Player, 
+    player::{Player, PlayerInput, Dash, FromPlayer},
     velocity::Velocity,
-    movable::Movable,
-    laser::Laser,
+    movable::{Movable, SimPosition},
+    laser::{Laser, Piercing, Homing},
+    weapon::{Weapon, WeaponKind, WEAPONS, CHARGE_FULL_TICKS},
+    enemy::Enemy,
+    hud::{TextWeapon, WeaponGauge, TextIntegrity},
+    health::{Damage, Health, Invulnerability},
     sprite_size::SpriteSize,
     state::InGameComponent
 };
 
+// variation de vitesse du pc par pas, la vitesse max vaut 1
+const ACCELERATION: f32 = 0.15;
+const DECELERATION: f32 = 0.25;
+// part de la hauteur de l'écran où le pc peut monter
+const PLAY_ZONE_HEIGHT: f32 = 0.35;
+const DASH_SPEED: f32 = 3.;
+const DASH_TICKS: u32 = 8;
+const DASH_COOLDOWN_TICKS: u32 = 45;
+// le pc reste invulnérable un peu après la fin du dash
+const DASH_GRACE_TICKS: u32 = 6;
+// vitesse horizontale des fichiers latéraux du tir rust, relative à `laser_velocity`
+const SPREAD_VELOCITY_X: f32 = 0.3;
+// part de la direction corrigée à chaque pas par la code review
+const HOMING_TURN_RATE: f32 = 0.08;
+// le pc clignote toutes les BLINK_TICKS pendant son invulnérabilité
+const BLINK_TICKS: u32 = 6;
+const GAUGE_WIDTH: f32 = 120.;
+const GAUGE_HEIGHT: f32 = 8.;
+const GAUGE_READY_COLOR: Color = Color::GREEN;
+const GAUGE_COOLDOWN_COLOR: Color = Color::GRAY;
+const GAUGE_CHARGE_COLOR: Color = Color::ORANGE;
+// teinte du pc du second joueur, pour les distinguer
+const SECOND_PLAYER_COLOR: Color = Color::rgb(0.6, 0.8, 1.);
+// changement d'arme, [1] à [4] pour le premier joueur et [5] à [8] pour le second
+const WEAPON_KEYS: [[KeyCode; 4]; 2] = [
+    [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4],
+    [KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8]
+];
+// largeur de la colonne du second joueur, à droite de l'écran
+const HUD_COLUMN_WIDTH: f32 = 260.;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
@@ -22,11 +59,34 @@ impl Plugin for PlayerPlugin {
             .add_system_set(
                 SystemSet::on_enter(AppState::InGame)
                     .with_system(player_spawn_system)
+                    .with_system(weapon_hud_spawn_system)
+                    .with_system(integrity_text_spawn_system)
             )
             .add_system_set(
                 SystemSet::on_update(AppState::InGame)
                     .with_system(player_keyboard_event_system)
+                    .with_system(update_weapon_text_system)
+                    .with_system(update_weapon_gauge_system)
+                    .with_system(update_integrity_text_system)
+                    .with_system(blink_invulnerable_player_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Record)
+                    .before(SimulationLabel::Movement)
+                    .with_system(player_movement_system)
                     .with_system(player_fire_system)
+                    .with_system(homing_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Movement)
+                    .before(SimulationLabel::Collision)
+                    .with_system(clamp_player_system)
             );
     }
 }
@@ -34,67 +94,490 @@ impl Plugin for PlayerPlugin {
 fn player_spawn_system(
     mut commands: Commands, 
     game_textures: Res<GameTextures>,
-    win_size: Res<WinSize>
+    win_size: Res<WinSize>,
+    config: Res<GameConfig>,
+    player_count: Res<PlayerCount>
+) {
+    for index in 0..player_count.0 {
+        spawn_player(&mut commands, &game_textures, &win_size, &config, index, player_count.0);
+    }
+}
+
+fn spawn_player(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    win_size: &WinSize,
+    config: &GameConfig,
+    index: usize,
+    player_count: usize
 ) {
     // add computer sprite
-    let bottom = -win_size.h / 2.;
+    let sprite_scale = config.sprite_scale;
+    // seul au centre, à deux chacun sur sa moitié d'écran
+    let x = if player_count > 1 {
+        (index as f32 - 0.5) * win_size.w / 2.
+    } else {
+        0.
+    };
+    let translation = Vec3::new(x, player_baseline(win_size, config), 10.);
+    let color = if index == 0 { Color::WHITE } else { SECOND_PLAYER_COLOR };
     commands
         .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color,
+                ..Default::default()
+            },
             texture: game_textures.computer.clone(),
             transform: Transform {
-                translation: Vec3::new(0., bottom + SPRITE_SIZE.1 / 2. + 5., 10.),
-                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+                translation,
+                scale: Vec3::new(sprite_scale, sprite_scale, 1.),
                 ..Default::default()
             },
             ..Default::default()
         })
-        .insert(Player)
-        .insert(SpriteSize::from(SPRITE_SIZE))
+        .insert(Player { index })
+        .insert(PlayerInput::default())
+        .insert(Weapon::default())
+        .insert(Health::new(config.player_integrity))
+        .insert(Invulnerability::default())
+        .insert(Dash::default())
+        .insert(SpriteSize::from(config.sprite_size))
         .insert(Movable {auto_despawn: false})
+        .insert(SimPosition::at(translation))
         .insert(Velocity {x: 0., y: 0.})
         .insert(InGameComponent);
 }
 
 fn player_keyboard_event_system(
-    kb: Res<Input<KeyCode>>,
-    mut query: Query<&mut Velocity, With<Player>>,
+    actions: ActionInput,
+    game_mode: Res<GameMode>,
+    mut query: Query<(&Player, &mut PlayerInput)>,
 ) {
-    if let Ok(mut velocity) = query.get_single_mut() {
-        velocity.x = if kb.pressed(KeyCode::Left) {
+    // pendant un replay les commandes viennent de l'enregistrement
+    if *game_mode == GameMode::Replay {
+        return;
+    }
+
+    for (player, mut input) in query.iter_mut() {
+        let index = player.index;
+        // les touches et la croix passent avant le stick
+        let stick = actions.player_stick(index);
+        input.x = if actions.player_pressed(index, Action::MoveLeft) {
+            -1.
+        } else if actions.player_pressed(index, Action::MoveRight) {
+            1.
+        } else {
+            stick.x
+        };
+        input.y = if actions.player_pressed(index, Action::MoveDown) {
             -1.
-        } else if kb.pressed(KeyCode::Right) {
+        } else if actions.player_pressed(index, Action::MoveUp) {
             1.
         } else {
-            0.
+            stick.y
         };
+
+        if actions.player_just_pressed(index, Action::Dash) {
+            input.dash = true;
+        }
+
+        if actions.player_just_pressed(index, Action::Fire) {
+            input.fire = true;
+        }
+        input.hold = actions.player_pressed(index, Action::Fire);
+
+        if let Some(weapon_keys) = WEAPON_KEYS.get(index) {
+            for (key, kind) in weapon_keys.iter().zip(WEAPONS) {
+                if actions.keyboard().just_pressed(*key) {
+                    input.weapon = Some(kind);
+                }
+            }
+        }
+    }
+}
+
+/// Hauteur du pc à l'apparition, en bas de l'écran : un ennemi qui passe dessous part en prod
+pub fn player_baseline(win_size: &WinSize, config: &GameConfig) -> f32 {
+    -win_size.h / 2. + config.sprite_size.1 / 2. + 5.
+}
+
+fn player_movement_system(
+    mut query: Query<(&mut PlayerInput, &mut Velocity, &mut Dash, &mut Invulnerability, &Health), With<Player>>
+) {
+    for (mut input, mut velocity, mut dash, mut invulnerability, health) in query.iter_mut() {
+        // un pc crashé en coop reste sur place
+        if health.current == 0 {
+            velocity.x = 0.;
+            velocity.y = 0.;
+            continue;
+        }
+
+        // meme vitesse en diagonale qu'en ligne droite
+        let direction = Vec2::new(input.x, input.y).normalize_or_zero();
+
+        dash.cooldown = dash.cooldown.saturating_sub(1);
+        if input.dash {
+            input.dash = false;
+            if dash.cooldown == 0 && direction != Vec2::ZERO {
+                dash.ticks = DASH_TICKS;
+                dash.cooldown = DASH_COOLDOWN_TICKS;
+                dash.direction = direction;
+                invulnerability.ticks = invulnerability.ticks.max(DASH_TICKS + DASH_GRACE_TICKS);
+            }
+        }
+
+        if dash.ticks > 0 {
+            dash.ticks -= 1;
+            velocity.x = dash.direction.x * DASH_SPEED;
+            velocity.y = dash.direction.y * DASH_SPEED;
+            continue;
+        }
+
+        let current = Vec2::new(velocity.x, velocity.y);
+        let rate = if direction == Vec2::ZERO { DECELERATION } else { ACCELERATION };
+        let delta = direction - current;
+        let next = if delta.length() <= rate {
+            direction
+        } else {
+            current + delta.normalize() * rate
+        };
+        velocity.x = next.x;
+        velocity.y = next.y;
+    }
+}
+
+/// Garde le pc dans la zone de jeu, en bas de l'écran
+fn clamp_player_system(
+    win_size: Res<WinSize>,
+    config: Res<GameConfig>,
+    mut query: Query<(&mut Transform, &mut SimPosition, &mut Velocity), With<Player>>
+) {
+    let half_width = config.sprite_size.0 * config.sprite_scale / 2.;
+    let (min_x, max_x) = (-win_size.w / 2. + half_width, win_size.w / 2. - half_width);
+    let min_y = player_baseline(&win_size, &config);
+    let max_y = (-win_size.h / 2. + win_size.h * PLAY_ZONE_HEIGHT).max(min_y);
+
+    for (mut transform, mut sim_position, mut velocity) in query.iter_mut() {
+        let position = &mut sim_position.current;
+        let clamped = Vec3::new(position.x.clamp(min_x, max_x), position.y.clamp(min_y, max_y), position.z);
+
+        // contre un bord le pc repart de zéro dans cette direction
+        if clamped.x != position.x {
+            velocity.x = 0.;
+        }
+        if clamped.y != position.y {
+            velocity.y = 0.;
+        }
+        *position = clamped;
+        transform.translation = clamped;
     }
 }
 
 fn player_fire_system(
     mut commands: Commands,
-    kb: Res<Input<KeyCode>>,
+    mut shot_fired: EventWriter<ShotFired>,
     game_textures: Res<GameTextures>,
-    query: Query<&Transform, With<Player>>
+    config: Res<GameConfig>,
+    power_ups: Res<PowerUps>,
+    mut query: Query<(&Player, &Transform, &Health, &mut PlayerInput, &mut Weapon)>
+) {
+    for (player, player_tf, health, mut input, mut weapon) in query.iter_mut() {
+        if health.current == 0 {
+            continue;
+        }
+
+        if let Some(kind) = input.weapon.take() {
+            weapon.kind = kind;
+            weapon.charge = 0;
+        }
+        weapon.cooldown = weapon.cooldown.saturating_sub(1);
+
+        let (x, y) = (player_tf.translation.x, player_tf.translation.y);
+        let translation = Vec3::new(x, y, 10.);
+
+        if input.fire {
+            input.fire = false;
+            // un tir trop rapproché est perdu, marteler la touche ne sert à rien
+            if weapon.cooldown == 0 {
+                weapon.cooldown = power_ups.fire_interval(weapon.kind);
+
+                let spread: &[f32] = match weapon.kind {
+                    WeaponKind::RustSpread => &[-SPREAD_VELOCITY_X, 0., SPREAD_VELOCITY_X],
+                    _ => &[0.]
+                };
+                for &velocity_x in spread {
+                    let laser = spawn_laser(&mut commands, &game_textures, &config, weapon.kind, translation, velocity_x);
+                    commands.entity(laser).insert(FromPlayer(player.index));
+                    shot_fired.send(ShotFired { player: player.index });
+                }
+            }
+        }
+
+        if input.hold {
+            weapon.charge = (weapon.charge + 1).min(CHARGE_FULL_TICKS);
+        } else if weapon.charge > 0 {
+            match weapon.charged_factor() {
+                // la charge relachée attend que l'arme soit prete, sinon charger en boucle contournerait le délai
+                Some(_) if weapon.cooldown > 0 => {}
+                Some(factor) => {
+                    let laser = spawn_charged_laser(&mut commands, &game_textures, &config, weapon.kind, translation, factor);
+                    commands.entity(laser).insert(FromPlayer(player.index));
+                    shot_fired.send(ShotFired { player: player.index });
+                    weapon.cooldown = power_ups.fire_interval(weapon.kind);
+                    weapon.charge = 0;
+                }
+                None => weapon.charge = 0
+            }
+        }
+    }
+}
+
+/// Oriente la code review vers l'ennemi le plus proche, sans changer sa vitesse
+fn homing_system(
+    mut laser_query: Query<(&Transform, &Homing, &mut Velocity), With<Laser>>,
+    enemy_query: Query<&Transform, With<Enemy>>
 ) {
-    if let Ok(player_tf) = query.get_single() {
-        if kb.just_pressed(KeyCode::X) {
-            let (x, y) = (player_tf.translation.x, player_tf.translation.y);
-
-            commands
-                .spawn_bundle(SpriteBundle {
-                    texture: game_textures.file_laser.clone(),
-                    transform: Transform {
-                        translation: Vec3::new(x, y, 10.),
-                        scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                        ..Default::default()
-                    },
+    for (laser_tf, homing, mut velocity) in laser_query.iter_mut() {
+        let laser_position = laser_tf.translation.truncate();
+        let target = enemy_query
+            .iter()
+            .map(|enemy_tf| enemy_tf.translation.truncate())
+            .filter(|enemy_position| enemy_position.y > laser_position.y)
+            .min_by(|a, b| a.distance_squared(laser_position).total_cmp(&b.distance_squared(laser_position)));
+
+        if let Some(target) = target {
+            let current = Vec2::new(velocity.x, velocity.y);
+            let wanted = (target - laser_position).normalize_or_zero() * current.length();
+            let steered = current.lerp(wanted, homing.turn_rate).normalize_or_zero() * current.length();
+            velocity.x = steered.x;
+            velocity.y = steered.y;
+        }
+    }
+}
+
+/// `velocity_x` est relative à `laser_velocity`, comme la vitesse verticale de 1
+pub fn spawn_laser(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    config: &GameConfig,
+    kind: WeaponKind,
+    translation: Vec3,
+    velocity_x: f32
+) -> Entity {
+    let sprite_scale = config.sprite_scale;
+    let speed = config.laser_velocity * kind.speed_factor();
+
+    let mut laser = commands.spawn_bundle(SpriteBundle {
+        texture: game_textures.projectile(kind),
+        transform: Transform {
+            translation,
+            scale: Vec3::new(sprite_scale, sprite_scale, 1.),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    laser
+        .insert(SpriteSize::from(config.sprite_size))
+        .insert(Movable {auto_despawn: true})
+        .insert(SimPosition::at(translation))
+        .insert(Laser)
+        .insert(Damage(kind.damage(config.laser_damage)))
+        .insert(Velocity {x: velocity_x * speed, y: speed})
+        .insert(InGameComponent);
+
+    match kind {
+        WeaponKind::Hotfix => { laser.insert(Piercing::default()); }
+        WeaponKind::CodeReview => { laser.insert(Homing { turn_rate: HOMING_TURN_RATE }); }
+        _ => {}
+    }
+
+    laser.id()
+}
+
+/// Un seul fichier, `factor` fois plus grand et plus puissant
+pub fn spawn_charged_laser(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    config: &GameConfig,
+    kind: WeaponKind,
+    translation: Vec3,
+    factor: f32
+) -> Entity {
+    let laser = spawn_laser(commands, game_textures, config, kind, translation, 0.);
+    let sprite_scale = config.sprite_scale * factor;
+    let damage = (kind.damage(config.laser_damage) as f32 * factor).round() as u32;
+
+    commands.entity(laser)
+        .insert(Transform {
+            translation,
+            scale: Vec3::new(sprite_scale, sprite_scale, 1.),
+            ..Default::default()
+        })
+        .insert(Damage(damage));
+
+    laser
+}
+
+/// Bord gauche de la colonne du joueur dans le hud : le premier à gauche, le second à droite
+pub fn hud_column_x(win_size: &WinSize, index: usize) -> f32 {
+    if index == 0 {
+        -win_size.w / 2.
+    } else {
+        win_size.w / 2. - HUD_COLUMN_WIDTH
+    }
+}
+
+/// "J1 " ou "J2 " devant les textes d'un joueur en coop, rien en solo
+pub fn hud_prefix(player_count: &PlayerCount, index: usize) -> String {
+    if player_count.is_coop() {
+        format!("J{} ", index + 1)
+    } else {
+        String::new()
+    }
+}
+
+fn weapon_hud_spawn_system(
+    mut commands: Commands,
+    win_size: Res<WinSize>,
+    game_fonts: Res<GameFonts>,
+    player_count: Res<PlayerCount>
+) {
+    for index in 0..player_count.0 {
+        // sous les vies
+        let pos_weapon = (hud_column_x(&win_size, index), win_size.h / 2. - 72.);
+        let prefix = hud_prefix(&player_count, index);
+        spawn_weapon_hud(&mut commands, &game_fonts, index, pos_weapon, prefix);
+    }
+}
+
+fn spawn_weapon_hud(
+    commands: &mut Commands,
+    game_fonts: &GameFonts,
+    index: usize,
+    pos_weapon: (f32, f32),
+    prefix: String
+) {
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: format!("{}arme : {}", prefix, WeaponKind::Scala.label()),
+                    style: TextStyle {
+                        color: Color::WHITE,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(pos_weapon.0, pos_weapon.1, 2.),
+            ..Default::default()
+        })
+        .insert(TextWeapon(index))
+        .insert(InGameComponent);
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: GAUGE_READY_COLOR,
+                custom_size: Some(Vec2::new(GAUGE_WIDTH, GAUGE_HEIGHT)),
+                anchor: Anchor::CenterLeft,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(pos_weapon.0, pos_weapon.1 - 32., 2.),
+            ..Default::default()
+        })
+        .insert(WeaponGauge(index))
+        .insert(InGameComponent);
+}
+
+fn update_weapon_text_system(
+    player_count: Res<PlayerCount>,
+    weapon_query: Query<(&Player, &Weapon), Changed<Weapon>>,
+    mut text_query: Query<(&TextWeapon, &mut Text)>
+) {
+    for (player, weapon) in weapon_query.iter() {
+        for (_, mut text) in text_query.iter_mut().filter(|(owner, _)| owner.0 == player.index) {
+            text.sections[0].value = format!("{}arme : {}", hud_prefix(&player_count, player.index), weapon.kind.label());
+        }
+    }
+}
+
+fn update_weapon_gauge_system(
+    weapon_query: Query<(&Player, &Weapon)>,
+    mut gauge_query: Query<(&WeaponGauge, &mut Sprite)>
+) {
+    for (player, weapon) in weapon_query.iter() {
+        let mut sprite = match gauge_query.iter_mut().find(|(owner, _)| owner.0 == player.index) {
+            Some((_, sprite)) => sprite,
+            None => continue
+        };
+
+        // la charge remplace le délai tant que la touche est maintenue
+        let (ratio, color) = if weapon.charged_factor().is_some() {
+            (weapon.charge_ratio(), GAUGE_CHARGE_COLOR)
+        } else if weapon.cooldown > 0 {
+            (weapon.readiness(), GAUGE_COOLDOWN_COLOR)
+        } else {
+            (1., GAUGE_READY_COLOR)
+        };
+        sprite.custom_size = Some(Vec2::new(GAUGE_WIDTH * ratio.max(0.05), GAUGE_HEIGHT));
+        sprite.color = color;
+    }
+}
+
+fn integrity_text_spawn_system(
+    mut commands: Commands,
+    win_size: Res<WinSize>,
+    game_fonts: Res<GameFonts>,
+    config: Res<GameConfig>,
+    player_count: Res<PlayerCount>
+) {
+    for index in 0..player_count.0 {
+        // sous les bonus
+        let pos_integrity = (hud_column_x(&win_size, index), win_size.h / 2. - 140.);
+
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: format!("{}integrite : {}/{}", hud_prefix(&player_count, index), config.player_integrity, config.player_integrity),
+                        style: TextStyle {
+                            color: Color::LIME_GREEN,
+                            font: game_fonts.text.clone(),
+                            font_size: 24.
+                        }
+                    }],
                     ..Default::default()
-                })
-                .insert(SpriteSize::from(SPRITE_SIZE))
-                .insert(Movable {auto_despawn: true})
-                .insert(Laser)
-                .insert(Velocity {x: 0., y: 1.})
-                .insert(InGameComponent);
+                },
+                transform: Transform::from_xyz(pos_integrity.0, pos_integrity.1, 2.),
+                ..Default::default()
+            })
+            .insert(TextIntegrity(index))
+            .insert(InGameComponent);
+    }
+}
+
+fn update_integrity_text_system(
+    player_count: Res<PlayerCount>,
+    player_query: Query<(&Player, &Health), Changed<Health>>,
+    mut text_query: Query<(&TextIntegrity, &mut Text)>
+) {
+    for (player, health) in player_query.iter() {
+        for (_, mut text) in text_query.iter_mut().filter(|(owner, _)| owner.0 == player.index) {
+            let section = &mut text.sections[0];
+            section.value = format!("{}integrite : {}/{}", hud_prefix(&player_count, player.index), health.current, health.max);
+            section.style.color = if health.current > 1 { Color::LIME_GREEN } else { Color::RED };
         }
     }
 }
+
+fn blink_invulnerable_player_system(
+    mut query: Query<(&Invulnerability, &Health, &mut Visibility), With<Player>>
+) {
+    for (invulnerability, health, mut visibility) in query.iter_mut() {
+        // un pc crashé en coop disparait
+        visibility.is_visible = health.current > 0
+            && (!invulnerability.is_active() || (invulnerability.ticks / BLINK_TICKS) % 2 == 0);
+    }
+}
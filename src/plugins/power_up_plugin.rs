@@ -0,0 +1,319 @@
+use bevy::prelude::*;
+use rand::prelude::*;
+use rand::distributions::WeightedIndex;
+
+use crate::{AppState, TIME_STEP};
+use crate::component::{
+    enemy::{Enemy, EnemyKind, SourceLabel},
+    health::Health,
+    hud::TextPowerUps,
+    movable::{Movable, SimPosition},
+    player::Player,
+    push_file::{PushFile, PowerUpKind},
+    sprite_size::SpriteSize,
+    state::InGameComponent,
+    temporary::Temporary,
+    velocity::Velocity,
+    weapon::{Weapon, WEAPONS}
+};
+use crate::events::EnemyDestroyed;
+use crate::plugins::collide_plugin::is_colliding;
+use crate::plugins::enemy::attach_enemy_label;
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::{
+    GameTextures,
+    GameFonts,
+    GameRng,
+    PowerUps,
+    Timer as MonTimer,
+    WinSize,
+    config::GameConfig
+};
+
+// secondes ajoutées avant la MEP
+const EXTRA_TIME: f32 = 5.;
+// durées des bonus, en pas de simulation
+const RAPID_FIRE_TICKS: u32 = 8 * 60;
+const SHIELD_TICKS: u32 = 10 * 60;
+const SCORE_BOOST_TICKS: u32 = 10 * 60;
+
+// poids dans le tirage du bonus d'un fichier poussé, l'arme est ensuite tirée parmi les autres que scala
+const POWER_UP_WEIGHTS: [(PowerUpKind, u32); 6] = [
+    (PowerUpKind::ExtraTime, 3),
+    (PowerUpKind::RapidFire, 3),
+    (PowerUpKind::Shield, 2),
+    (PowerUpKind::ScoreBoost, 2),
+    (PowerUpKind::Hotfix, 1),
+    (PowerUpKind::Weapon(WEAPONS[0]), 3)
+];
+
+// les fichiers poussés tirent dans GameRng après les ennemis, leur ordre avec le texte "PUSH !!" doit etre fixe
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct DropLabel;
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(PowerUps::default())
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(restart_power_ups_system)
+                    .with_system(power_up_text_spawn_system)
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::InGame)
+                    .with_system(update_power_up_text_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Collision)
+                    .with_system(pick_up_system.label(SimulationLabel::PickUp))
+                    .with_system(tick_power_ups_system.after(SimulationLabel::PickUp))
+                    .with_system(spawn_push_file_system.label(DropLabel).after(SimulationLabel::PickUp).after(SimulationLabel::Spawn))
+                    .with_system(spawn_push_text_system.after(DropLabel))
+            );
+    }
+}
+
+fn restart_power_ups_system(
+    mut power_ups: ResMut<PowerUps>
+) {
+    *power_ups = PowerUps::default();
+}
+
+fn tick_power_ups_system(
+    mut power_ups: ResMut<PowerUps>
+) {
+    power_ups.tick();
+}
+
+#[allow(clippy::type_complexity)]
+fn pick_up_system(
+    mut commands: Commands,
+    mut enemy_destroyed: EventWriter<EnemyDestroyed>,
+    mut timer: ResMut<MonTimer>,
+    mut power_ups: ResMut<PowerUps>,
+    mut player_query: Query<(&Player, &Transform, &SpriteSize, &Health, &mut Weapon), Without<Enemy>>,
+    push_query: Query<(Entity, &Transform, &SpriteSize, &PushFile)>,
+    mut enemy_query: Query<(Entity, &Transform, &EnemyKind, &mut Health, Option<&SourceLabel>), With<Enemy>>
+) {
+    for (push_entity, push_tf, push_size, push_file) in push_query.iter() {
+        // en coop le premier pc dans l'ordre des joueurs ramasse le fichier
+        let picker = player_query
+            .iter_mut()
+            .filter(|(_, player_tf, player_size, health, _)| {
+                health.current > 0 && is_colliding(player_tf, player_size, push_tf, push_size)
+            })
+            .min_by_key(|(player, ..)| player.index);
+        let (player, _, _, _, mut weapon) = match picker {
+            Some(picker) => picker,
+            None => continue
+        };
+        commands.entity(push_entity).despawn_recursive();
+
+        match push_file.power_up {
+            PowerUpKind::ExtraTime => timer.0 += EXTRA_TIME,
+            PowerUpKind::RapidFire => power_ups.rapid_fire = RAPID_FIRE_TICKS,
+            PowerUpKind::Shield => power_ups.shield = SHIELD_TICKS,
+            PowerUpKind::ScoreBoost => power_ups.score_boost = SCORE_BOOST_TICKS,
+            PowerUpKind::Weapon(kind) => {
+                weapon.kind = kind;
+                weapon.charge = 0;
+            }
+            PowerUpKind::Hotfix => {
+                // les ennemis deja détruits par un tir de ce pas sont à zéro point de vie
+                for (enemy_entity, enemy_tf, enemy_kind, mut enemy_health, source_label) in enemy_query.iter_mut() {
+                    if enemy_health.current == 0 {
+                        continue;
+                    }
+                    enemy_health.current = 0;
+                    commands.entity(enemy_entity).despawn_recursive();
+
+                    enemy_destroyed.send(EnemyDestroyed {
+                        entity: enemy_entity,
+                        kind: *enemy_kind,
+                        position: enemy_tf.translation,
+                        source: source_label.map(|label| label.0),
+                        cleared: true,
+                        player: Some(player.index)
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn spawn_push_file_system(
+    mut commands: Commands,
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    mut game_rng: ResMut<GameRng>,
+    game_textures: Res<GameTextures>,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    config: Res<GameConfig>
+) {
+    for event in enemy_destroyed.iter().filter(|event| !event.cleared) {
+        let power_up = random_power_up(&mut game_rng.rng);
+        spawn_push_file(&mut commands, &game_textures, &game_fonts, &config, power_up, event.position);
+    }
+
+    // de temps en temps un bonus tombe du haut de l'écran
+    if config.power_up_drop_interval > 0.
+        && game_rng.rng.gen_bool((TIME_STEP / config.power_up_drop_interval).min(1.) as f64) {
+        let w_span = win_size.w / 2. - config.sprite_size.0;
+        let x = game_rng.rng.gen_range(-w_span..w_span);
+        let power_up = random_power_up(&mut game_rng.rng);
+        spawn_push_file(&mut commands, &game_textures, &game_fonts, &config, power_up, Vec3::new(x, win_size.h / 2., 10.));
+    }
+}
+
+fn random_power_up(rng: &mut impl Rng) -> PowerUpKind {
+    let weights = WeightedIndex::new(POWER_UP_WEIGHTS.iter().map(|(_, weight)| *weight)).unwrap();
+    match POWER_UP_WEIGHTS[weights.sample(rng)].0 {
+        PowerUpKind::Weapon(_) => PowerUpKind::Weapon(WEAPONS[rng.gen_range(1..WEAPONS.len())]),
+        power_up => power_up
+    }
+}
+
+/// Fichier bonus qui tombe vers le joueur, teinté et étiqueté selon son bonus
+pub fn spawn_push_file(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    game_fonts: &GameFonts,
+    config: &GameConfig,
+    power_up: PowerUpKind,
+    position: Vec3
+) -> Entity {
+    let translation = Vec3::new(position.x, position.y, 10.);
+    let push_file = commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: power_up.color(),
+                ..Default::default()
+            },
+            transform: Transform {
+                translation,
+                ..Default::default()
+            },
+            texture: game_textures.push_file.clone(),
+            ..Default::default()
+        })
+        .insert(SpriteSize::from(config.sprite_size))
+        .insert(Velocity {x: 0., y: -config.push_file_velocity})
+        .insert(Movable { auto_despawn: true })
+        .insert(SimPosition::at(translation))
+        .insert(PushFile { power_up })
+        .insert(InGameComponent)
+        .id();
+
+    attach_enemy_label(commands, push_file, power_up.label().to_string(), game_fonts, config);
+    push_file
+}
+
+fn spawn_push_text_system(
+    mut commands: Commands,
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    mut game_rng: ResMut<GameRng>,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>
+) {
+    for _ in enemy_destroyed.iter() {
+        spawn_push_text(&mut commands, &mut game_rng.rng, &game_fonts, &win_size);
+    }
+}
+
+fn spawn_push_text(
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+    game_fonts: &Res<GameFonts>,
+    win_size: &Res<WinSize>
+) {
+    let w_span = win_size.w / 2. - 64.;
+    let h_span = win_size.h / 2. - 64.;
+    let x = rng.gen_range(-w_span..w_span);
+    let y = rng.gen_range(-h_span..h_span);// rng.gen_range(-h_span..h_span);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: "PUSH !!".to_string(),
+                    style: TextStyle {
+                        color: Color::rgb(0., 0.3, 0.),
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(x, y, 2.),
+            ..Default::default()
+        })
+        .insert(Temporary { duration: 1., current_time: 0.})
+        .insert(InGameComponent);
+}
+
+fn power_up_text_spawn_system(
+    mut commands: Commands,
+    win_size: Res<WinSize>,
+    game_fonts: Res<GameFonts>
+) {
+    // sous la jauge de l'arme
+    let pos_power_ups = (-win_size.w / 2., win_size.h / 2. - 116.);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 20.
+                    }
+                }],
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(pos_power_ups.0, pos_power_ups.1, 2.),
+            ..Default::default()
+        })
+        .insert(TextPowerUps)
+        .insert(InGameComponent);
+}
+
+fn update_power_up_text_system(
+    power_ups: Res<PowerUps>,
+    mut query: Query<&mut Text, With<TextPowerUps>>
+) {
+    if !power_ups.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = query.get_single_mut() {
+        let active = [
+            (PowerUpKind::RapidFire, power_ups.rapid_fire),
+            (PowerUpKind::Shield, power_ups.shield),
+            (PowerUpKind::ScoreBoost, power_ups.score_boost)
+        ]
+            .iter()
+            .filter(|(_, ticks)| *ticks > 0)
+            .map(|(power_up, ticks)| format!("{} {:.0}s", power_up.label(), (*ticks as f32 * TIME_STEP).ceil()))
+            .collect::<Vec<String>>();
+
+        text.sections[0].value = if active.is_empty() {
+            String::new()
+        } else {
+            format!("bonus : {}", active.join(", "))
+        };
+    }
+}
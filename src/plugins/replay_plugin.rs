@@ -0,0 +1,394 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::{
+    menu::{ActionPrompt, TextReplayList},
+    player::{Player, PlayerInput},
+    state::{InGameComponent, InReplayComponent}
+};
+use crate::factory::replay_factory::{list_replays, load_replay, save_replay, LAST_REPLAY_FILE};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::{
+    GameMode,
+    GameRng,
+    PlayerCount,
+    Scoring,
+    WinSize,
+    GameFonts,
+    config::GameConfig,
+    gamepad::ActionInput,
+    keymap::Action,
+    sprint::Sprint,
+    replay::{Replay, ReplayBrowser, ReplayPlayback, ReplayRecorder, Playback, encode_input, decode_input, pack_input, unpack_input}
+};
+
+// un replay par touche [1] à [9]
+const REPLAY_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1, KeyCode::Key2, KeyCode::Key3,
+    KeyCode::Key4, KeyCode::Key5, KeyCode::Key6,
+    KeyCode::Key7, KeyCode::Key8, KeyCode::Key9
+];
+
+pub struct ReplayPlugin;
+
+/// Réglages du lancement que le replay remplace le temps de la lecture
+#[derive(SystemParam)]
+struct LaunchSettings<'w, 's> {
+    commands: Commands<'w, 's>,
+    config: ResMut<'w, GameConfig>,
+    game_mode: ResMut<'w, GameMode>,
+    player_count: ResMut<'w, PlayerCount>,
+    sprint: Option<Res<'w, Sprint>>
+}
+
+impl<'w, 's> LaunchSettings<'w, 's> {
+    /// La manche doit se jouer avec les réglages, les joueurs et le sprint de l'enregistrement
+    fn start_playback(&mut self, replay: Replay) -> Playback {
+        let saved_config = std::mem::replace(&mut *self.config, replay.config.clone());
+        let saved_players = std::mem::replace(&mut self.player_count.0, replay.players);
+        let saved_mode = std::mem::replace(&mut *self.game_mode, GameMode::Replay);
+        let saved_sprint = self.sprint.as_ref().map(|sprint| sprint.commits.clone());
+
+        // appliqué avant le pas fixe de la frame, qui fait apparaitre les ennemis
+        match replay.sprint() {
+            Some(sprint) => self.commands.insert_resource(sprint),
+            None => self.commands.remove_resource::<Sprint>()
+        }
+        Playback::new(replay, saved_config, saved_mode, saved_players, saved_sprint)
+    }
+
+    fn stop_playback(&mut self, playback: Playback) {
+        *self.config = playback.saved_config;
+        *self.game_mode = playback.saved_mode;
+        self.player_count.0 = playback.saved_players;
+        match playback.saved_sprint {
+            Some(commits) => self.commands.insert_resource(Sprint::new(commits)),
+            None => self.commands.remove_resource::<Sprint>()
+        }
+    }
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ReplayRecorder::default())
+            .insert_resource(ReplayPlayback::default())
+            .insert_resource(ReplayBrowser::default())
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(restart_replay_system)
+                    .with_system(write_replay_banner_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::Restore)
+                    .with_system(playback_input_system.label(SimulationLabel::Input))
+                    .with_system(record_input_system.label(SimulationLabel::Record).after(SimulationLabel::Input))
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::EndGame)
+                    .with_system(save_last_replay_system)
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::MainMenu)
+                    .with_system(stop_playback_system)
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::Replay)
+                    .with_system(write_replays_system)
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::Replay)
+                    .with_system(select_replay_system)
+                    .with_system(update_replay_list_system.after(select_replay_system))
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::Replay)
+                    .with_system(on_exit_replays_system)
+            );
+    }
+}
+
+/// Enregistrement de la partie qui vient de se terminer, `None` pendant un replay
+pub fn finished_replay(
+    game_mode: &GameMode,
+    game_rng: &GameRng,
+    scoring: &Scoring,
+    recorder: &ReplayRecorder
+) -> Option<Replay> {
+    if *game_mode == GameMode::Replay {
+        return None;
+    }
+    let replay = Replay::new(game_rng.seed, scoring.points, recorder.config.clone(), recorder.players, &recorder.inputs);
+    Some(replay.with_mode(recorder.mode, &recorder.sprint_commits))
+}
+
+fn restart_replay_system(
+    config: Res<GameConfig>,
+    player_count: Res<PlayerCount>,
+    game_mode: Res<GameMode>,
+    sprint: Option<Res<Sprint>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut replay_playback: ResMut<ReplayPlayback>
+) {
+    // les réglages peuvent être rechargés en cours de manche, on garde ceux du départ
+    recorder.config = config.clone();
+    recorder.players = player_count.0;
+    recorder.mode = *game_mode;
+    recorder.sprint_commits = sprint.map_or_else(Vec::new, |sprint| sprint.commits.clone());
+    recorder.inputs.clear();
+
+    if let Some(playback) = replay_playback.current.as_mut() {
+        playback.cursor = 0;
+    }
+}
+
+fn write_replay_banner_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    game_mode: Res<GameMode>
+) {
+    if *game_mode != GameMode::Replay {
+        return;
+    }
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: "REPLAY".to_string(),
+                    style: TextStyle {
+                        color: Color::CYAN,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 24., 2.),
+            ..Default::default()
+        })
+        .insert(InGameComponent);
+}
+
+fn playback_input_system(
+    mut replay_playback: ResMut<ReplayPlayback>,
+    mut query: Query<(&Player, &mut PlayerInput)>
+) {
+    if let Some(playback) = replay_playback.current.as_mut() {
+        let packed = playback.next();
+        for (player, mut input) in query.iter_mut() {
+            decode_input(unpack_input(player.index, packed), &mut input);
+        }
+    }
+}
+
+fn record_input_system(
+    game_mode: Res<GameMode>,
+    mut recorder: ResMut<ReplayRecorder>,
+    query: Query<(&Player, &PlayerInput)>
+) {
+    if *game_mode == GameMode::Replay {
+        return;
+    }
+
+    let packed = query
+        .iter()
+        .fold(0, |packed, (player, input)| packed | pack_input(player.index, encode_input(input)));
+    recorder.inputs.push(packed);
+}
+
+fn save_last_replay_system(
+    game_mode: Res<GameMode>,
+    game_rng: Res<GameRng>,
+    scoring: Res<Scoring>,
+    recorder: Res<ReplayRecorder>
+) {
+    if let Some(replay) = finished_replay(&game_mode, &game_rng, &scoring, &recorder) {
+        if let Err(e) = save_replay(&replay, LAST_REPLAY_FILE) {
+            error!("impossible d'enregistrer le replay ({})", e);
+        }
+    }
+}
+
+fn stop_playback_system(
+    mut launch: LaunchSettings,
+    mut replay_playback: ResMut<ReplayPlayback>
+) {
+    if let Some(playback) = replay_playback.current.take() {
+        launch.stop_playback(playback);
+    }
+}
+
+fn write_replays_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    mut browser: ResMut<ReplayBrowser>
+) {
+    browser.entries = list_replays()
+        .into_iter()
+        .filter_map(|path| match load_replay(&path) {
+            Ok(replay) => {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                Some((name, replay))
+            }
+            Err(e) => {
+                warn!("replay ignore : {}", e);
+                None
+            }
+        })
+        .take(REPLAY_KEYS.len())
+        .collect();
+    browser.selected = 0;
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: "Replays".to_string(),
+                    style: TextStyle {
+                        color: Color::RED,
+                        font: game_fonts.text.clone(),
+                        font_size: 36.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., win_size.h / 2., 1.),
+            ..Default::default()
+        })
+        .insert(InReplayComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: replay_list_text(&browser),
+                    style: TextStyle {
+                        color: Color::ORANGE,
+                        font: game_fonts.text.clone(),
+                        font_size: 24.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Center,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(TextReplayList)
+        .insert(InReplayComponent);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: String::new(),
+                    style: TextStyle {
+                        color: Color::YELLOW,
+                        font: game_fonts.text.clone(),
+                        font_size: 20.
+                    }
+                }],
+                alignment: TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., -win_size.h / 2. + 36., 1.),
+            ..Default::default()
+        })
+        .insert(ActionPrompt::new("{}/{} choisir, {} ou [1-9] pour revoir, {} pour revenir au menu",
+            &[Action::MoveUp, Action::MoveDown, Action::Confirm, Action::Back]))
+        .insert(InReplayComponent);
+}
+
+fn select_replay_system(
+    mut app_state: ResMut<State<AppState>>,
+    actions: ActionInput,
+    mut launch: LaunchSettings,
+    mut browser: ResMut<ReplayBrowser>,
+    mut replay_playback: ResMut<ReplayPlayback>
+) {
+    if actions.just_pressed(Action::Back) {
+        let _ = app_state.set(AppState::MainMenu);
+        return;
+    }
+
+    let count = browser.entries.len();
+    if count == 0 {
+        return;
+    }
+    if actions.just_pressed(Action::MoveUp) {
+        browser.selected = (browser.selected + count - 1) % count;
+    } else if actions.just_pressed(Action::MoveDown) {
+        browser.selected = (browser.selected + 1) % count;
+    }
+
+    let selected = REPLAY_KEYS
+        .iter()
+        .position(|key| actions.keyboard().just_pressed(*key))
+        .or_else(|| actions.just_pressed(Action::Confirm).then(|| browser.selected))
+        .filter(|&index| index < count);
+
+    if let Some(index) = selected {
+        let (name, replay) = browser.entries.swap_remove(index);
+        info!("lecture du replay {} (seed : {}, mode : {})", name, replay.seed, replay.mode.label());
+        replay_playback.current = Some(launch.start_playback(replay));
+        let _ = app_state.set(AppState::InGame);
+    }
+}
+
+fn replay_list_text(browser: &ReplayBrowser) -> String {
+    if browser.entries.is_empty() {
+        return "aucun replay pour l'instant".to_string();
+    }
+    browser.entries
+        .iter()
+        .enumerate()
+        .map(|(i, (name, replay))| {
+            let marker = if i == browser.selected { ">" } else { " " };
+            format!("{} [{}] {:<24} {:>6} pts", marker, i + 1, name, replay.score)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// seulement quand la sélection change, le texte est écrit une première fois à l'ouverture
+fn update_replay_list_system(
+    browser: Res<ReplayBrowser>,
+    mut query: Query<&mut Text, With<TextReplayList>>
+) {
+    if !browser.is_changed() {
+        return;
+    }
+    if let Ok(mut text) = query.get_single_mut() {
+        text.sections[0].value = replay_list_text(&browser);
+    }
+}
+
+fn on_exit_replays_system(
+    mut commands: Commands,
+    mut browser: ResMut<ReplayBrowser>,
+    query: Query<Entity, With<InReplayComponent>>
+) {
+    browser.entries.clear();
+    query.iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+}
@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::component::{enemy::SourceLabel, state::InEndgameComponent};
+use crate::events::EnemyDestroyed;
+use crate::plugins::enemy::attach_enemy_label;
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::{GameFonts, WinSize, config::GameConfig, scan::ScanComments};
+
+// au-delà, le texte du commentaire est coupé sous le sprite
+const LABEL_TEXT_MAX_LEN: usize = 28;
+// commentaires listés à l'écran de fin, les autres sont dans les logs
+const END_SCREEN_MAX_COMMENTS: usize = 4;
+
+/// Mode `--scan <dossier>` : les ennemis portent les TODO/FIXME du dossier,
+/// sans effet si la ressource `ScanComments` est absente
+pub struct ScanPlugin;
+
+impl Plugin for ScanPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(restart_scan_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::PickUp)
+                    .with_system(record_fixed_comments_system)
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::EndGame)
+                    .with_system(write_fixed_comments_system)
+            );
+    }
+}
+
+/// Affiche le commentaire sous le sprite de l'ennemi
+pub fn attach_source_label(
+    commands: &mut Commands,
+    enemy: Entity,
+    index: usize,
+    scan_comments: &ScanComments,
+    game_fonts: &GameFonts,
+    config: &GameConfig
+) {
+    let comment = &scan_comments.all[index];
+    let mut text = comment.text.chars().take(LABEL_TEXT_MAX_LEN).collect::<String>();
+    if comment.text.chars().count() > LABEL_TEXT_MAX_LEN {
+        text.push_str("...");
+    }
+
+    commands.entity(enemy).insert(SourceLabel(index));
+    attach_enemy_label(commands, enemy, format!("{} {}\n{}", comment.tag, comment.location(), text), game_fonts, config);
+}
+
+fn restart_scan_system(scan_comments: Option<ResMut<ScanComments>>) {
+    if let Some(mut scan_comments) = scan_comments {
+        scan_comments.restart();
+    }
+}
+
+fn record_fixed_comments_system(
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    scan_comments: Option<ResMut<ScanComments>>
+) {
+    if let Some(mut scan_comments) = scan_comments {
+        for index in enemy_destroyed.iter().filter_map(|event| event.source) {
+            scan_comments.fixed.push(index);
+        }
+    }
+}
+
+fn write_fixed_comments_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    scan_comments: Option<Res<ScanComments>>
+) {
+    let scan_comments = match scan_comments {
+        Some(scan_comments) => scan_comments,
+        None => return
+    };
+
+    let fixed = scan_comments.fixed
+        .iter()
+        .map(|&index| &scan_comments.all[index])
+        .collect::<Vec<_>>();
+    for comment in fixed.iter() {
+        info!("fix : {} {} {}", comment.tag, comment.location(), comment.text);
+    }
+
+    let mut lines = fixed
+        .iter()
+        .take(END_SCREEN_MAX_COMMENTS)
+        .map(|comment| format!("{} {}", comment.tag, comment.location()))
+        .collect::<Vec<String>>();
+    if fixed.len() > END_SCREEN_MAX_COMMENTS {
+        lines.push(format!("... et {} autres", fixed.len() - END_SCREEN_MAX_COMMENTS));
+    }
+    let value = if fixed.is_empty() {
+        "aucun commentaire fix".to_string()
+    } else {
+        format!("{} commentaires fix :\n{}", fixed.len(), lines.join("\n"))
+    };
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value,
+                    style: TextStyle {
+                        color: Color::WHITE,
+                        font: game_fonts.text.clone(),
+                        font_size: 16.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Top,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., win_size.h / 2. - 8., 1.),
+            ..Default::default()
+        })
+        .insert(InEndgameComponent);
+}
@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use crate::{AppState, TIME_STEP};
+use crate::component::{hud::TextPlayerScore, player::ScoreBugFix};
+use crate::events::{EnemyDestroyed, EnemyEscaped, EnemyHit, ShotFired, LaserMissed};
+use crate::resources::{PlayerScore, PowerUps, Scoring, config::GameConfig};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+
+// bonus accordé toutes les STREAK_LENGTH touches consécutives
+const STREAK_LENGTH: u32 = 10;
+const STREAK_BONUS: u32 = 10;
+
+// ordre fixe de traitement d'un pas, pour que le score d'une partie rejouée soit identique
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+enum ScoringLabel {
+    Timeout,
+    Miss,
+    Hit
+}
+
+/// Temps écoulé depuis la dernière touche, hors de `Scoring` pour ne pas le marquer modifié à chaque pas
+#[derive(Default)]
+struct SinceLastHit(f32);
+
+pub struct ScoringPlugin;
+
+impl Plugin for ScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(SinceLastHit::default())
+            .add_event::<EnemyHit>()
+            .add_event::<ShotFired>()
+            .add_event::<LaserMissed>()
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(restart_scoring_system)
+            )
+            .add_system_set_to_stage(
+                // le combo ne doit pas expirer pendant la pause
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::PickUp)
+                    .with_system(count_shots_system)
+                    .with_system(count_escaped_system)
+                    .with_system(combo_timeout_system.label(ScoringLabel::Timeout))
+                    .with_system(combo_miss_system.label(ScoringLabel::Miss).after(ScoringLabel::Timeout))
+                    .with_system(combo_hit_system.label(ScoringLabel::Hit).after(ScoringLabel::Miss))
+                    .with_system(score_enemy_destroyed_system.after(ScoringLabel::Hit))
+            )
+            .add_system(update_score_text_system);
+    }
+}
+
+fn restart_scoring_system(
+    mut scoring: ResMut<Scoring>,
+    mut since_last_hit: ResMut<SinceLastHit>
+) {
+    *scoring = Scoring::default();
+    since_last_hit.0 = 0.;
+}
+
+fn count_shots_system(
+    mut shot_fired: EventReader<ShotFired>,
+    mut scoring: ResMut<Scoring>
+) {
+    for event in shot_fired.iter() {
+        scoring.shots_fired += 1;
+        if let Some(player) = scoring.players.get_mut(event.player) {
+            player.shots_fired += 1;
+        }
+    }
+}
+
+fn count_escaped_system(
+    mut enemy_escaped: EventReader<EnemyEscaped>,
+    mut scoring: ResMut<Scoring>
+) {
+    for _ in enemy_escaped.iter() {
+        scoring.escaped += 1;
+    }
+}
+
+fn combo_hit_system(
+    mut enemy_hit: EventReader<EnemyHit>,
+    mut scoring: ResMut<Scoring>,
+    mut since_last_hit: ResMut<SinceLastHit>
+) {
+    for event in enemy_hit.iter() {
+        scoring.hits += 1;
+        if let Some(player) = event.player.and_then(|index| scoring.players.get_mut(index)) {
+            player.hits += 1;
+        }
+        scoring.combo += 1;
+        scoring.best_combo = scoring.best_combo.max(scoring.combo);
+        since_last_hit.0 = 0.;
+
+        if scoring.combo % STREAK_LENGTH == 0 {
+            let bonus = STREAK_BONUS * scoring.combo / STREAK_LENGTH;
+            scoring.streak_bonus += bonus;
+            scoring.points += bonus;
+        }
+    }
+}
+
+fn combo_miss_system(
+    mut laser_missed: EventReader<LaserMissed>,
+    mut scoring: ResMut<Scoring>
+) {
+    if laser_missed.iter().count() > 0 {
+        scoring.combo = 0;
+    }
+}
+
+fn combo_timeout_system(
+    config: Res<GameConfig>,
+    mut scoring: ResMut<Scoring>,
+    mut since_last_hit: ResMut<SinceLastHit>
+) {
+    if scoring.combo == 0 {
+        return;
+    }
+
+    since_last_hit.0 += TIME_STEP;
+    if since_last_hit.0 > config.combo_window {
+        scoring.combo = 0;
+    }
+}
+
+fn score_enemy_destroyed_system(
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    power_ups: Res<PowerUps>,
+    mut scoring: ResMut<Scoring>
+) {
+    for event in enemy_destroyed.iter() {
+        let base = event.kind.score();
+        let with_combo = base * scoring.multiplier();
+        let total = with_combo * power_ups.score_factor();
+
+        scoring.bug_fix += 1;
+        scoring.base_points += base;
+        scoring.combo_bonus += with_combo - base;
+        scoring.power_up_bonus += total - with_combo;
+        scoring.points += total;
+
+        // les bonus de série restent à l'équipe
+        if let Some(player) = event.player.and_then(|index| scoring.players.get_mut(index)) {
+            player.bug_fix += 1;
+            player.points += total;
+        }
+    }
+}
+
+fn update_score_text_system(
+    scoring: Res<Scoring>,
+    mut score_bug_fix_query: Query<&mut Text, (With<ScoreBugFix>, Without<TextPlayerScore>)>,
+    mut player_score_query: Query<(&TextPlayerScore, &mut Text)>
+) {
+    if !scoring.is_changed() {
+        return;
+    }
+
+    for mut text in score_bug_fix_query.iter_mut() {
+        text.sections[0].value = format!("bug fix : {} ({} pts) x{}", scoring.bug_fix, scoring.points, scoring.multiplier());
+    }
+
+    for (player_score, mut text) in player_score_query.iter_mut() {
+        if let Some(player) = scoring.players.get(player_score.0) {
+            text.sections[0].value = player_score_text(player_score.0, player);
+        }
+    }
+}
+
+/// Ligne du hud d'un joueur en coop
+pub fn player_score_text(index: usize, player: &PlayerScore) -> String {
+    format!("J{} : {} pts ({} bugs fix)", index + 1, player.points, player.bug_fix)
+}
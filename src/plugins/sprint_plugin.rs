@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+
+use crate::{AppState, TIME_STEP};
+use crate::component::state::InEndgameComponent;
+use crate::events::{EnemyDestroyed, EnemyEscaped, PlayerHit};
+use crate::plugins::fixed_step_plugin::{FixedUpdateStage, SimulationLabel, run_if_in_game};
+use crate::resources::{GameFonts, WinSize, Timer as MonTimer, sprint::Sprint};
+
+// commits listés à l'écran de fin, les autres sont dans les logs
+const END_SCREEN_MAX_COMMITS: usize = 4;
+
+#[derive(SystemLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct SprintTrackingLabel;
+
+/// Mode `--sprint <dépot>` : chaque commit est une vague d'ennemis,
+/// sans effet si la ressource `Sprint` est absente
+pub struct SprintPlugin;
+
+impl Plugin for SprintPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system_set(
+                SystemSet::on_enter(AppState::InGame)
+                    .with_system(restart_sprint_system)
+            )
+            .add_system_set_to_stage(
+                FixedUpdateStage,
+                SystemSet::new()
+                    .with_run_criteria(run_if_in_game)
+                    .after(SimulationLabel::PickUp)
+                    .with_system(track_sprint_enemies_system.label(SprintTrackingLabel))
+                    .with_system(sprint_done_system.after(SprintTrackingLabel).after(SimulationLabel::Timer))
+            )
+            .add_system_set(
+                SystemSet::on_enter(AppState::EndGame)
+                    .with_system(write_sprint_summary_system)
+            );
+    }
+}
+
+fn restart_sprint_system(sprint: Option<ResMut<Sprint>>) {
+    if let Some(mut sprint) = sprint {
+        sprint.restart();
+    }
+}
+
+fn track_sprint_enemies_system(
+    mut enemy_destroyed: EventReader<EnemyDestroyed>,
+    mut enemy_escaped: EventReader<EnemyEscaped>,
+    mut player_hit: EventReader<PlayerHit>,
+    sprint: Option<ResMut<Sprint>>
+) {
+    let mut sprint = match sprint {
+        Some(sprint) => sprint,
+        None => return
+    };
+
+    for event in enemy_destroyed.iter() {
+        if let Some(index) = sprint.alive.remove(&event.entity) {
+            sprint.destroyed[index] += 1;
+        }
+    }
+    for event in enemy_escaped.iter() {
+        if let Some(index) = sprint.alive.remove(&event.entity) {
+            sprint.escaped[index] += 1;
+        }
+    }
+    // un fichier écrasé sur le pc n'est pas livré non plus
+    for event in player_hit.iter() {
+        if let Some(index) = sprint.alive.remove(&event.enemy) {
+            sprint.escaped[index] += 1;
+        }
+    }
+}
+
+fn sprint_done_system(
+    sprint: Option<Res<Sprint>>,
+    mut timer: ResMut<MonTimer>
+) {
+    // toutes les vagues sont passées : la MEP part en avance
+    if sprint.map_or(false, |sprint| sprint.is_over()) && timer.0 >= 0. {
+        timer.0 = -TIME_STEP;
+    }
+}
+
+fn write_sprint_summary_system(
+    mut commands: Commands,
+    game_fonts: Res<GameFonts>,
+    win_size: Res<WinSize>,
+    sprint: Option<Res<Sprint>>
+) {
+    let sprint = match sprint {
+        Some(sprint) => sprint,
+        None => return
+    };
+
+    let shipped = (0..sprint.commits.len())
+        .filter(|&index| sprint.is_shipped(index))
+        .collect::<Vec<usize>>();
+    for (index, commit) in sprint.commits.iter().enumerate() {
+        let status = if sprint.is_shipped(index) { "livre" } else { "pas livre" };
+        info!("{} : {} {} ({})", status, commit.hash, commit.subject, commit.author);
+    }
+
+    let mut lines = shipped
+        .iter()
+        .take(END_SCREEN_MAX_COMMITS)
+        .map(|&index| {
+            let commit = &sprint.commits[index];
+            format!("{} {}", commit.hash, commit.subject.chars().take(32).collect::<String>())
+        })
+        .collect::<Vec<String>>();
+    if shipped.len() > END_SCREEN_MAX_COMMITS {
+        lines.push(format!("... et {} autres", shipped.len() - END_SCREEN_MAX_COMMITS));
+    }
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text {
+                sections: vec![TextSection {
+                    value: format!("commits livres : {}/{}\n{}", shipped.len(), sprint.commits.len(), lines.join("\n")),
+                    style: TextStyle {
+                        color: Color::WHITE,
+                        font: game_fonts.text.clone(),
+                        font_size: 16.
+                    }
+                }],
+                alignment: TextAlignment {
+                    vertical: VerticalAlign::Top,
+                    horizontal: HorizontalAlign::Center
+                },
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0., win_size.h / 2. - 8., 1.),
+            ..Default::default()
+        })
+        .insert(InEndgameComponent);
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::component::enemy::EnemyKind;
+
+/// Réglages du jeu chargés depuis `assets/config.ron`.
+/// Tout champ absent du fichier garde sa valeur par défaut.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub sprite_size: (f32, f32),
+    pub sprite_scale: f32,
+    pub base_speed: f32,
+    pub enemy_max: u32,
+    pub round_time: f32,
+    pub lives: u32,
+    /// chocs avec un fichier ennemi encaissés par le pc avant de crasher
+    pub player_integrity: u32,
+    pub enemy_velocity: f32,
+    pub laser_velocity: f32,
+    pub push_file_velocity: f32,
+    /// secondes en moyenne entre deux fichiers bonus tombés au hasard, 0 pour aucun
+    pub power_up_drop_interval: f32,
+    pub laser_damage: u32,
+    pub combo_window: f32,
+    /// graine des manches, tirée au hasard à chaque manche si absente
+    pub seed: Option<u64>,
+    pub font: String,
+    pub textures: TexturesConfig,
+    pub enemy_weights: Vec<EnemyWeight>,
+    pub difficulty: Vec<DifficultyStep>
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TexturesConfig {
+    pub computer: String,
+    pub fixme_file: String,
+    pub flaky_test: String,
+    pub merge_conflict: String,
+    pub legacy_code: String,
+    pub null_pointer: String,
+    pub file_laser: String,
+    pub rust_file: String,
+    pub hotfix_file: String,
+    pub code_review_file: String,
+    pub push_file: String,
+    pub bg: String
+}
+
+/// Poids d'un type de fichier ennemi dans le tirage des apparitions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnemyWeight {
+    pub kind: EnemyKind,
+    pub weight: u32
+}
+
+/// Point de la courbe de difficulté, les valeurs sont interpolées entre deux points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyStep {
+    /// avancement de la manche, de 0 (début) à 1 (MEP)
+    pub at: f32,
+    /// multiplie `enemy_max`
+    pub enemy_max_factor: f32,
+    /// multiplie `enemy_velocity`
+    pub speed_factor: f32,
+    /// délai minimum entre deux apparitions de fichiers fixme, en secondes
+    pub spawn_interval: f32
+}
+
+impl Default for DifficultyStep {
+    fn default() -> Self {
+        DifficultyStep {
+            at: 0.,
+            enemy_max_factor: 1.,
+            speed_factor: 1.,
+            spawn_interval: 0.
+        }
+    }
+}
+
+impl DifficultyStep {
+    fn lerp(&self, other: &DifficultyStep, t: f32) -> DifficultyStep {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        DifficultyStep {
+            at: mix(self.at, other.at),
+            enemy_max_factor: mix(self.enemy_max_factor, other.enemy_max_factor),
+            speed_factor: mix(self.speed_factor, other.speed_factor),
+            spawn_interval: mix(self.spawn_interval, other.spawn_interval)
+        }
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            sprite_size: (64., 64.),
+            sprite_scale: 1.,
+            base_speed: 500.,
+            enemy_max: 2,
+            round_time: 30.,
+            lives: 3,
+            player_integrity: 3,
+            enemy_velocity: 0.5,
+            laser_velocity: 1.,
+            push_file_velocity: 0.8,
+            power_up_drop_interval: 15.,
+            laser_damage: 1,
+            combo_window: 2.,
+            seed: None,
+            font: "PaintDrops.ttf".to_string(),
+            textures: TexturesConfig::default(),
+            enemy_weights: vec![
+                EnemyWeight { kind: EnemyKind::Fixme, weight: 6 },
+                EnemyWeight { kind: EnemyKind::FlakyTest, weight: 3 },
+                EnemyWeight { kind: EnemyKind::MergeConflict, weight: 2 },
+                EnemyWeight { kind: EnemyKind::LegacyCode, weight: 1 },
+                EnemyWeight { kind: EnemyKind::NullPointer, weight: 2 }
+            ],
+            difficulty: vec![
+                DifficultyStep { at: 0., enemy_max_factor: 1., speed_factor: 1., spawn_interval: 1. },
+                DifficultyStep { at: 0.5, enemy_max_factor: 1.5, speed_factor: 1.3, spawn_interval: 0.7 },
+                DifficultyStep { at: 0.85, enemy_max_factor: 2.5, speed_factor: 1.7, spawn_interval: 0.4 },
+                DifficultyStep { at: 1., enemy_max_factor: 3.5, speed_factor: 2.2, spawn_interval: 0.2 }
+            ]
+        }
+    }
+}
+
+impl Default for TexturesConfig {
+    fn default() -> Self {
+        TexturesConfig {
+            computer: "pc-codeur.png".to_string(),
+            fixme_file: "fixme-file.png".to_string(),
+            flaky_test: "flaky-test-file.png".to_string(),
+            merge_conflict: "merge-conflict-file.png".to_string(),
+            legacy_code: "legacy-code-file.png".to_string(),
+            null_pointer: "null-pointer-file.png".to_string(),
+            file_laser: "scala-file.png".to_string(),
+            rust_file: "rust-file.png".to_string(),
+            hotfix_file: "hotfix-file.png".to_string(),
+            code_review_file: "code-review-file.png".to_string(),
+            push_file: "push-file.png".to_string(),
+            bg: "bg.png".to_string()
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = vec![];
+
+        if self.sprite_size.0 <= 0. || self.sprite_size.1 <= 0. {
+            errors.push(format!("sprite_size doit etre positif (recu {:?})", self.sprite_size));
+        }
+        if self.sprite_scale <= 0. {
+            errors.push(format!("sprite_scale doit etre positif (recu {})", self.sprite_scale));
+        }
+        if self.base_speed <= 0. {
+            errors.push(format!("base_speed doit etre positif (recu {})", self.base_speed));
+        }
+        if self.enemy_max == 0 {
+            errors.push("enemy_max doit valoir au moins 1".to_string());
+        }
+        if self.round_time <= 0. {
+            errors.push(format!("round_time doit etre positif (recu {})", self.round_time));
+        }
+        if self.lives == 0 {
+            errors.push("lives doit valoir au moins 1".to_string());
+        }
+        if self.player_integrity == 0 {
+            errors.push("player_integrity doit valoir au moins 1".to_string());
+        }
+        if self.enemy_velocity <= 0. {
+            errors.push(format!("enemy_velocity doit etre positif (recu {})", self.enemy_velocity));
+        }
+        if self.laser_velocity <= 0. {
+            errors.push(format!("laser_velocity doit etre positif (recu {})", self.laser_velocity));
+        }
+        if self.push_file_velocity <= 0. {
+            errors.push(format!("push_file_velocity doit etre positif (recu {})", self.push_file_velocity));
+        }
+        if self.power_up_drop_interval < 0. {
+            errors.push(format!("power_up_drop_interval ne peut pas etre negatif (recu {})", self.power_up_drop_interval));
+        }
+        if self.laser_damage == 0 {
+            errors.push("laser_damage doit valoir au moins 1".to_string());
+        }
+        if self.combo_window <= 0. {
+            errors.push(format!("combo_window doit etre positif (recu {})", self.combo_window));
+        }
+        if self.font.is_empty() {
+            errors.push("font ne peut pas etre vide".to_string());
+        }
+        if self.enemy_weights.iter().all(|entry| entry.weight == 0) {
+            errors.push("enemy_weights doit contenir au moins un poids non nul".to_string());
+        }
+        for (i, step) in self.difficulty.iter().enumerate() {
+            if !(0. ..=1.).contains(&step.at) {
+                errors.push(format!("difficulty[{}].at doit etre entre 0 et 1 (recu {})", i, step.at));
+            }
+            if i > 0 && step.at < self.difficulty[i - 1].at {
+                errors.push(format!("difficulty[{}].at doit etre apres le point precedent", i));
+            }
+            if step.enemy_max_factor <= 0. || step.speed_factor <= 0. {
+                errors.push(format!("difficulty[{}] : les facteurs doivent etre positifs", i));
+            }
+            if step.spawn_interval < 0. {
+                errors.push(format!("difficulty[{}].spawn_interval ne peut pas etre negatif", i));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(errors))
+        }
+    }
+
+    /// Difficulté à un avancement donné de la manche (0 au début, 1 à la MEP)
+    pub fn difficulty_at(&self, progress: f32) -> DifficultyStep {
+        let curve = &self.difficulty;
+        match curve.iter().position(|step| step.at >= progress) {
+            None => curve.last().cloned().unwrap_or_default(),
+            Some(0) => curve[0].clone(),
+            Some(i) => {
+                let (from, to) = (&curve[i - 1], &curve[i]);
+                from.lerp(to, (progress - from.at) / (to.at - from.at))
+            }
+        }
+    }
+
+    /// Liste lisible des réglages qui diffèrent entre `self` et `other`
+    pub fn changes(&self, other: &GameConfig) -> Vec<String> {
+        let mut changes = vec![];
+
+        macro_rules! compare {
+            ($($field:ident),*) => {
+                $(
+                    if self.$field != other.$field {
+                        changes.push(format!("{} : {:?} -> {:?}", stringify!($field), self.$field, other.$field));
+                    }
+                )*
+            };
+        }
+
+        compare!(
+            sprite_size, sprite_scale, base_speed, enemy_max, round_time, lives, player_integrity,
+            enemy_velocity, laser_velocity, push_file_velocity, power_up_drop_interval, laser_damage, combo_window, seed, font, textures,
+            enemy_weights, difficulty
+        );
+
+        changes
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(String),
+    Parse(String),
+    Invalid(Vec<String>)
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(message) => write!(f, "lecture impossible : {}", message),
+            ConfigError::Parse(message) => write!(f, "fichier mal forme : {}", message),
+            ConfigError::Invalid(errors) => write!(f, "valeurs invalides :\n  - {}", errors.join("\n  - "))
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::resources::PlayerCount;
+use crate::resources::keymap::{Action, KeyMap, key_label};
+
+// en dessous, le stick est considéré au repos
+const STICK_DEAD_ZONE: f32 = 0.3;
+// sin(22.5°) : le stick est ramené à l'une des huit directions
+const STICK_SNAP: f32 = 0.38;
+
+/// Dernier appareil utilisé, pour afficher les touches du clavier ou les boutons de la manette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    Keyboard,
+    Gamepad
+}
+
+impl Default for InputDevice {
+    fn default() -> Self {
+        InputDevice::Keyboard
+    }
+}
+
+/// Manette qui commande le pc, la première branchée
+#[derive(Debug, Default)]
+pub struct Controllers {
+    pub gamepad: Option<Gamepad>,
+    pub device: InputDevice
+}
+
+/// Bouton de la manette pour chaque action, non modifiable
+pub fn gamepad_button(action: Action) -> GamepadButtonType {
+    match action {
+        Action::MoveLeft => GamepadButtonType::DPadLeft,
+        Action::MoveRight => GamepadButtonType::DPadRight,
+        Action::MoveUp => GamepadButtonType::DPadUp,
+        Action::MoveDown => GamepadButtonType::DPadDown,
+        Action::Fire => GamepadButtonType::South,
+        Action::Dash => GamepadButtonType::RightTrigger,
+        Action::Confirm => GamepadButtonType::South,
+        Action::Back => GamepadButtonType::East,
+        Action::Pause => GamepadButtonType::Start,
+        Action::HighScores => GamepadButtonType::North,
+        Action::Replays => GamepadButtonType::West,
+        Action::Controls => GamepadButtonType::Select,
+        Action::TogglePlayers => GamepadButtonType::LeftTrigger,
+        Action::Restart => GamepadButtonType::West,
+        Action::Quit => GamepadButtonType::Select,
+        Action::ResetKeys => GamepadButtonType::North
+    }
+}
+
+fn button_name(button: GamepadButtonType) -> &'static str {
+    match button {
+        GamepadButtonType::South => "A",
+        GamepadButtonType::East => "B",
+        GamepadButtonType::West => "X",
+        GamepadButtonType::North => "Y",
+        GamepadButtonType::LeftTrigger => "LB",
+        GamepadButtonType::RightTrigger => "RB",
+        GamepadButtonType::Start => "Start",
+        GamepadButtonType::Select => "Select",
+        GamepadButtonType::DPadLeft => "croix gauche",
+        GamepadButtonType::DPadRight => "croix droite",
+        GamepadButtonType::DPadUp => "croix haut",
+        GamepadButtonType::DPadDown => "croix bas",
+        _ => "?"
+    }
+}
+
+/// Touche ou bouton de `action` tel qu'affiché dans les textes : [X] au clavier, (A) à la manette
+pub fn action_glyph(key_map: &KeyMap, device: InputDevice, action: Action) -> String {
+    match device {
+        InputDevice::Keyboard => format!("[{}]", key_label(key_map.key(action))),
+        InputDevice::Gamepad => format!("({})", button_name(gamepad_button(action)))
+    }
+}
+
+/// Remplace chaque "{}" de `template` par la touche de l'action suivante
+pub fn prompt_text(template: &str, actions: &[Action], key_map: &KeyMap, device: InputDevice) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (part, action) in parts.zip(actions) {
+        text.push_str(&action_glyph(key_map, device, *action));
+        text.push_str(part);
+    }
+    text
+}
+
+/// Direction du stick gauche, nulle dans la zone morte.
+/// Elle est ramenée à -1, 0 ou 1 par axe comme au clavier, pour que les replays restent exacts
+pub fn stick_direction(axes: &Axis<GamepadAxis>, gamepad: Gamepad) -> Vec2 {
+    let stick = Vec2::new(
+        axes.get(GamepadAxis(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.),
+        axes.get(GamepadAxis(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.)
+    );
+    if stick.length() < STICK_DEAD_ZONE {
+        return Vec2::ZERO;
+    }
+
+    let direction = stick.normalize();
+    let snap = |value: f32| if value.abs() < STICK_SNAP { 0. } else { value.signum() };
+    Vec2::new(snap(direction.x), snap(direction.y))
+}
+
+/// Actions lues au clavier et sur la manette active
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    kb: ResMut<'w, Input<KeyCode>>,
+    buttons: ResMut<'w, Input<GamepadButton>>,
+    axes: Res<'w, Axis<GamepadAxis>>,
+    key_map: ResMut<'w, KeyMap>,
+    controllers: Res<'w, Controllers>,
+    player_count: Res<'w, PlayerCount>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>
+}
+
+impl<'w, 's> ActionInput<'w, 's> {
+    fn button(&self, action: Action) -> Option<GamepadButton> {
+        self.controllers.gamepad.map(|gamepad| GamepadButton(gamepad, gamepad_button(action)))
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.key_map.pressed(&self.kb, action)
+            || self.button(action).map_or(false, |button| self.buttons.pressed(button))
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.key_map.just_pressed(&self.kb, action) || self.pad_just_pressed(action)
+    }
+
+    /// Seulement le bouton de la manette, quand la touche du clavier sert aussi à écrire
+    pub fn pad_just_pressed(&self, action: Action) -> bool {
+        self.button(action).map_or(false, |button| self.buttons.just_pressed(button))
+    }
+
+    /// Oublie l'appui sur `action`, pour qu'il ne soit pas relu après un changement d'état dans la meme frame
+    pub fn reset(&mut self, action: Action) {
+        let key = self.key_map.key(action);
+        self.kb.reset(key);
+        if let Some(button) = self.button(action) {
+            self.buttons.reset(button);
+        }
+    }
+
+    pub fn stick(&self) -> Vec2 {
+        self.controllers.gamepad.map_or(Vec2::ZERO, |gamepad| stick_direction(&self.axes, gamepad))
+    }
+
+    // en coop la manette revient au second joueur, le premier garde les touches de l'écran des commandes
+    fn has_gamepad(&self, player: usize) -> bool {
+        player + 1 == self.player_count.0
+    }
+
+    /// Action tenue par le joueur `player`, à ses touches ou à la manette
+    pub fn player_pressed(&self, player: usize, action: Action) -> bool {
+        self.key_map.player_key(player, action).map_or(false, |key| self.kb.pressed(key))
+            || (self.has_gamepad(player) && self.button(action).map_or(false, |button| self.buttons.pressed(button)))
+    }
+
+    pub fn player_just_pressed(&self, player: usize, action: Action) -> bool {
+        self.key_map.player_key(player, action).map_or(false, |key| self.kb.just_pressed(key))
+            || (self.has_gamepad(player) && self.pad_just_pressed(action))
+    }
+
+    pub fn player_stick(&self, player: usize) -> Vec2 {
+        if self.has_gamepad(player) { self.stick() } else { Vec2::ZERO }
+    }
+
+    /// Touches fixes, hors des actions
+    pub fn keyboard(&self) -> &Input<KeyCode> {
+        &self.kb
+    }
+
+    /// Oublie l'appui sur une touche lue directement au clavier
+    pub fn reset_key(&mut self, key: KeyCode) {
+        self.kb.reset(key);
+    }
+
+    pub fn key_map(&self) -> &KeyMap {
+        &self.key_map
+    }
+
+    /// Pour l'écran des commandes, qui change les touches
+    pub fn key_map_mut(&mut self) -> &mut KeyMap {
+        &mut self.key_map
+    }
+
+    pub fn device(&self) -> InputDevice {
+        self.controllers.device
+    }
+
+    pub fn glyph(&self, action: Action) -> String {
+        action_glyph(&self.key_map, self.controllers.device, action)
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+pub const HIGH_SCORES_MAX: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: u32,
+    pub date: String,
+    pub mode: String,
+    /// fichier du replay de la partie, dans le dossier des replays
+    #[serde(default)]
+    pub replay: Option<String>
+}
+
+/// Top 10 local, trié du meilleur au moins bon score
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    pub entries: Vec<HighScoreEntry>
+}
+
+impl HighScores {
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORES_MAX
+                || self.entries.last().map_or(true, |last| score > last.score))
+    }
+
+    /// Insère le score à sa place, à score égal le plus ancien reste devant.
+    /// Renvoie les scores sortis du classement, dont les replays ne sont plus gardés
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Vec<HighScoreEntry> {
+        let position = self.entries
+            .iter()
+            .position(|current| entry.score > current.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+        if self.entries.len() > HIGH_SCORES_MAX {
+            self.entries.split_off(HIGH_SCORES_MAX)
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Saisie du nom du joueur sur l'écran de fin quand le score entre dans le top 10
+#[derive(Default)]
+pub struct NameEntry {
+    pub name: String,
+    pub active: bool
+}
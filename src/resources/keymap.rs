@@ -0,0 +1,293 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::{Input, KeyCode};
+use serde::{Deserialize, Serialize};
+
+/// Commande du jeu, reliée à une touche modifiable depuis l'écran des commandes.
+/// Les commandes des menus après `Pause` gardent leur touche fixe, mais passent aussi par la manette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Fire,
+    Dash,
+    Confirm,
+    Back,
+    Pause,
+    HighScores,
+    Replays,
+    Controls,
+    TogglePlayers,
+    Restart,
+    Quit,
+    ResetKeys
+}
+
+/// Actions dans l'ordre de l'écran des commandes
+pub const ACTIONS: [Action; 9] = [
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Fire,
+    Action::Dash,
+    Action::Confirm,
+    Action::Back,
+    Action::Pause
+];
+
+/// Actions du second joueur en coop ; valider, retour et pause restent communs
+pub const SECOND_PLAYER_ACTIONS: [Action; 6] = [
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Fire,
+    Action::Dash
+];
+
+/// Lignes de l'écran des commandes : (joueur, action)
+pub const BINDINGS: [(usize, Action); 15] = [
+    (0, Action::MoveLeft),
+    (0, Action::MoveRight),
+    (0, Action::MoveUp),
+    (0, Action::MoveDown),
+    (0, Action::Fire),
+    (0, Action::Dash),
+    (0, Action::Confirm),
+    (0, Action::Back),
+    (0, Action::Pause),
+    (1, Action::MoveLeft),
+    (1, Action::MoveRight),
+    (1, Action::MoveUp),
+    (1, Action::MoveDown),
+    (1, Action::Fire),
+    (1, Action::Dash)
+];
+
+impl Action {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "gauche",
+            Action::MoveRight => "droite",
+            Action::MoveUp => "haut",
+            Action::MoveDown => "bas",
+            Action::Fire => "tir",
+            Action::Dash => "dash",
+            Action::Confirm => "valider",
+            Action::Back => "retour",
+            Action::Pause => "pause",
+            Action::HighScores => "scores",
+            Action::Replays => "replays",
+            Action::Controls => "commandes",
+            Action::TogglePlayers => "mode",
+            Action::Restart => "recommencer",
+            Action::Quit => "quitter",
+            Action::ResetKeys => "par defaut"
+        }
+    }
+
+    pub fn is_rebindable(&self) -> bool {
+        ACTIONS.contains(self)
+    }
+
+    fn default_key(&self) -> KeyCode {
+        match self {
+            Action::MoveLeft => KeyCode::Left,
+            Action::MoveRight => KeyCode::Right,
+            Action::MoveUp => KeyCode::Up,
+            Action::MoveDown => KeyCode::Down,
+            Action::Fire => KeyCode::X,
+            Action::Dash => KeyCode::LShift,
+            Action::Confirm => KeyCode::Space,
+            Action::Back => KeyCode::Escape,
+            Action::Pause => KeyCode::P,
+            Action::HighScores => KeyCode::H,
+            Action::Replays => KeyCode::R,
+            Action::Controls => KeyCode::C,
+            Action::TogglePlayers => KeyCode::Key2,
+            Action::Restart => KeyCode::R,
+            Action::Quit => KeyCode::M,
+            Action::ResetKeys => KeyCode::Delete
+        }
+    }
+}
+
+/// Touches par défaut du second joueur, à gauche du clavier
+fn second_player_default_key(action: Action) -> Option<KeyCode> {
+    match action {
+        Action::MoveLeft => Some(KeyCode::Q),
+        Action::MoveRight => Some(KeyCode::D),
+        Action::MoveUp => Some(KeyCode::Z),
+        Action::MoveDown => Some(KeyCode::S),
+        Action::Fire => Some(KeyCode::A),
+        Action::Dash => Some(KeyCode::E),
+        _ => None
+    }
+}
+
+/// Touches qui peuvent etre associées à une action, et donc enregistrées par leur nom.
+/// Les touches fixes n'y sont pas, sinon une seule touche déclencherait deux commandes :
+/// [H], [R] et [C] du menu, [R] et [M] de la pause, [1] à [9] des armes et des replays,
+/// et [Suppr] qui remet les touches par défaut sur l'écran des commandes
+const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::A, KeyCode::B, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+    KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::N,
+    KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::S, KeyCode::T, KeyCode::U,
+    KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    KeyCode::Key0,
+    KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down,
+    KeyCode::Space, KeyCode::Return, KeyCode::Escape, KeyCode::Back, KeyCode::Tab,
+    KeyCode::LShift, KeyCode::RShift, KeyCode::LControl, KeyCode::RControl, KeyCode::LAlt, KeyCode::RAlt,
+    KeyCode::Insert, KeyCode::Home, KeyCode::End, KeyCode::PageUp, KeyCode::PageDown,
+    KeyCode::Comma, KeyCode::Period, KeyCode::Semicolon, KeyCode::Colon, KeyCode::Slash, KeyCode::Minus,
+    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+    KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12
+];
+
+pub fn is_bindable(key: KeyCode) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
+
+/// Nom d'une touche dans le fichier de réglages et à l'écran, par exemple "LShift"
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+/// Nom affiché dans les textes, "2" plutot que "Key2"
+pub fn key_label(key: KeyCode) -> String {
+    let name = key_name(key);
+    match name.strip_prefix("Key") {
+        Some(digit) if !digit.is_empty() => digit.to_string(),
+        _ => name
+    }
+}
+
+pub fn parse_key_name(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS.iter().copied().find(|&key| key_name(key) == name)
+}
+
+/// Touche associée à chaque action, et à chaque action du second joueur
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    keys: HashMap<Action, KeyCode>,
+    second_player: HashMap<Action, KeyCode>
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap {
+            keys: ACTIONS.iter().map(|&action| (action, action.default_key())).collect(),
+            second_player: SECOND_PLAYER_ACTIONS
+                .iter()
+                .filter_map(|&action| Some((action, second_player_default_key(action)?)))
+                .collect()
+        }
+    }
+}
+
+impl KeyMap {
+    /// Touche choisie, ou touche fixe pour les commandes des menus
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys.get(&action).copied().unwrap_or_else(|| action.default_key())
+    }
+
+    pub fn pressed(&self, kb: &Input<KeyCode>, action: Action) -> bool {
+        kb.pressed(self.key(action))
+    }
+
+    pub fn just_pressed(&self, kb: &Input<KeyCode>, action: Action) -> bool {
+        kb.just_pressed(self.key(action))
+    }
+
+    /// Touche de `action` pour le joueur `player`, `None` si le second joueur ne l'a pas
+    pub fn player_key(&self, player: usize, action: Action) -> Option<KeyCode> {
+        if player == 0 {
+            Some(self.key(action))
+        } else {
+            self.second_player.get(&action).copied()
+        }
+    }
+
+    /// Associe `key` à `action` ; l'action qui l'utilisait déjà récupère l'ancienne touche
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        self.bind_player(0, action, key);
+    }
+
+    /// Meme chose pour le joueur `player`, l'échange se fait aussi avec les touches de l'autre joueur
+    pub fn bind_player(&mut self, player: usize, action: Action, key: KeyCode) {
+        let previous = match self.player_key(player, action) {
+            Some(previous) => previous,
+            None => return
+        };
+        if let Some(other_key) = self.keys.values_mut().chain(self.second_player.values_mut()).find(|other_key| **other_key == key) {
+            *other_key = previous;
+        }
+        let keys = if player == 0 { &mut self.keys } else { &mut self.second_player };
+        keys.insert(action, key);
+    }
+
+    /// Touches par nom d'action, telles qu'enregistrées dans le fichier de réglages
+    pub fn names(&self) -> BTreeMap<Action, String> {
+        self.keys.iter().map(|(&action, &key)| (action, key_name(key))).collect()
+    }
+
+    pub fn second_player_names(&self) -> BTreeMap<Action, String> {
+        self.second_player.iter().map(|(&action, &key)| (action, key_name(key))).collect()
+    }
+
+    pub fn settings(&self) -> Settings {
+        Settings {
+            keys: self.names(),
+            second_player: self.second_player_names()
+        }
+    }
+
+    /// Reprend les touches de `names` ; une touche inconnue ou deja prise laisse celle par défaut.
+    /// Renvoie les noms ignorés
+    pub fn from_names(names: &BTreeMap<Action, String>) -> (Self, Vec<String>) {
+        KeyMap::from_settings(&Settings { keys: names.clone(), ..Default::default() })
+    }
+
+    /// Meme chose pour les touches des deux joueurs
+    pub fn from_settings(settings: &Settings) -> (Self, Vec<String>) {
+        let mut key_map = KeyMap::default();
+        let mut ignored = vec![];
+        key_map.read_names(0, &settings.keys, &mut ignored);
+        key_map.read_names(1, &settings.second_player, &mut ignored);
+        (key_map, ignored)
+    }
+
+    fn read_names(&mut self, player: usize, names: &BTreeMap<Action, String>, ignored: &mut Vec<String>) {
+        for (&action, name) in names {
+            match parse_key_name(name) {
+                Some(key) if action.is_rebindable()
+                    && self.player_key(player, action).is_some()
+                    && !names.iter().any(|(&other, other_name)| other != action && other_name == name) => {
+                    self.bind_player(player, action, key);
+                }
+                _ => ignored.push(name.clone())
+            }
+        }
+    }
+}
+
+/// Contenu du fichier de réglages de l'utilisateur
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub keys: BTreeMap<Action, String>,
+    /// absent des réglages d'avant les touches du second joueur
+    pub second_player: BTreeMap<Action, String>
+}
+
+/// Ligne de `BINDINGS` sélectionnée sur l'écran des commandes, `waiting` pendant qu'on attend la nouvelle touche
+#[derive(Debug, Default)]
+pub struct ControlsScreen {
+    pub selected: usize,
+    pub waiting: bool
+}
@@ -1,27 +1,257 @@
-use bevy::prelude::{Handle, Image};
+pub mod config;
+pub mod highscores;
+pub mod replay;
+pub mod scan;
+pub mod sprint;
+pub mod keymap;
+pub mod gamepad;
+
+use bevy::prelude::{Font, Handle, Image};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::component::{enemy::EnemyKind, weapon::WeaponKind};
 
 pub struct WinSize {
     pub w: f32,
     pub h: f32
 }
 
+#[derive(Default)]
 pub struct GameTextures {
     pub computer: Handle<Image>,
     pub file_laser: Handle<Image>,
+    pub rust_file: Handle<Image>,
+    pub hotfix_file: Handle<Image>,
+    pub code_review_file: Handle<Image>,
     pub fixme_file: Handle<Image>,
+    pub flaky_test: Handle<Image>,
+    pub merge_conflict: Handle<Image>,
+    pub legacy_code: Handle<Image>,
+    pub null_pointer: Handle<Image>,
     pub push_file: Handle<Image>,
     pub bg: Handle<Image>
 }
 
+#[derive(Default)]
+pub struct GameFonts {
+    pub text: Handle<Font>
+}
+
+impl GameTextures {
+    pub fn enemy(&self, kind: EnemyKind) -> Handle<Image> {
+        match kind {
+            EnemyKind::Fixme => self.fixme_file.clone(),
+            EnemyKind::FlakyTest => self.flaky_test.clone(),
+            EnemyKind::MergeConflict | EnemyKind::ConflictHalf => self.merge_conflict.clone(),
+            EnemyKind::LegacyCode => self.legacy_code.clone(),
+            EnemyKind::NullPointer => self.null_pointer.clone()
+        }
+    }
+
+    pub fn projectile(&self, kind: WeaponKind) -> Handle<Image> {
+        match kind {
+            WeaponKind::Scala => self.file_laser.clone(),
+            WeaponKind::RustSpread => self.rust_file.clone(),
+            WeaponKind::Hotfix => self.hotfix_file.clone(),
+            WeaponKind::CodeReview => self.code_review_file.clone()
+        }
+    }
+}
+
 pub struct Scoring {
-    pub bug_fix: u32
+    pub bug_fix: u32,
+    pub points: u32,
+    // détail des points pour l'écran de fin
+    pub base_points: u32,
+    pub combo_bonus: u32,
+    // doublement du bonus "score x2", à part du combo
+    pub power_up_bonus: u32,
+    pub streak_bonus: u32,
+    // touches consécutives sans rater, remis à zéro après un raté ou une pause trop longue
+    pub combo: u32,
+    pub best_combo: u32,
+    pub shots_fired: u32,
+    pub hits: u32,
+    // ennemis passés sous le pc du joueur
+    pub escaped: u32,
+    // part de chaque joueur, le combo reste commun
+    pub players: [PlayerScore; MAX_PLAYERS]
+}
+
+/// Détail du score d'un joueur en coop
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerScore {
+    pub points: u32,
+    pub bug_fix: u32,
+    pub shots_fired: u32,
+    pub hits: u32
 }
 
+impl PlayerScore {
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            0.
+        } else {
+            self.hits as f32 / self.shots_fired as f32 * 100.
+        }
+    }
+}
+
+// une touche consécutive sur COMBO_STEP fait monter le multiplicateur
+const COMBO_STEP: u32 = 5;
+const MAX_MULTIPLIER: u32 = 5;
+
+impl Scoring {
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.combo / COMBO_STEP).min(MAX_MULTIPLIER)
+    }
+
+    /// Pourcentage de tirs qui ont touché un ennemi
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            0.
+        } else {
+            self.hits as f32 / self.shots_fired as f32 * 100.
+        }
+    }
+}
 
 impl Default for Scoring {
     fn default() -> Self {
-        Scoring {bug_fix: 0}
+        Scoring {
+            bug_fix: 0,
+            points: 0,
+            base_points: 0,
+            combo_bonus: 0,
+            power_up_bonus: 0,
+            streak_bonus: 0,
+            combo: 0,
+            best_combo: 0,
+            shots_fired: 0,
+            hits: 0,
+            escaped: 0,
+            players: Default::default()
+        }
     }
 }
 
 pub struct Timer(pub f32);
+
+/// Seule source d'aléatoire du gameplay, resemée au début de chaque manche
+/// pour qu'une partie puisse etre rejouée à l'identique avec la meme graine
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed)
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        *self = GameRng::new(seed);
+    }
+}
+
+pub struct Lives(pub u32);
+
+pub const MAX_PLAYERS: usize = 2;
+
+/// Nombre de pc en jeu : 1, ou 2 en coop sur le meme clavier
+pub struct PlayerCount(pub usize);
+
+impl PlayerCount {
+    pub fn is_coop(&self) -> bool {
+        self.0 > 1
+    }
+}
+
+/// Difficulté courante, recalculée à partir du temps restant avant la MEP
+pub struct Difficulty {
+    pub enemy_max: u32,
+    pub speed_factor: f32,
+    pub spawn_interval: f32
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty {
+            enemy_max: 1,
+            speed_factor: 1.,
+            spawn_interval: 0.
+        }
+    }
+}
+
+/// Bonus ramassés encore actifs, en pas de simulation restants
+#[derive(Default)]
+pub struct PowerUps {
+    pub rapid_fire: u32,
+    pub shield: u32,
+    pub score_boost: u32
+}
+
+impl PowerUps {
+    /// délai entre deux tirs de `kind`, divisé par deux en tir rapide
+    pub fn fire_interval(&self, kind: WeaponKind) -> u32 {
+        if self.rapid_fire > 0 {
+            (kind.fire_interval() / 2).max(1)
+        } else {
+            kind.fire_interval()
+        }
+    }
+
+    pub fn score_factor(&self) -> u32 {
+        if self.score_boost > 0 { 2 } else { 1 }
+    }
+
+    pub fn tick(&mut self) {
+        self.rapid_fire = self.rapid_fire.saturating_sub(1);
+        self.shield = self.shield.saturating_sub(1);
+        self.score_boost = self.score_boost.saturating_sub(1);
+    }
+}
+
+/// Temps écoulé depuis la dernière apparition d'un fichier fixme
+pub struct EnemySpawnTimer(pub f32);
+
+/// Mode de jeu de la partie en cours, enregistré avec les scores et les replays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    Classic,
+    Replay,
+    /// ennemis tirés des commentaires d'un dossier (`--scan`)
+    Scan,
+    /// vagues tirées des commits d'un dépot git (`--sprint`)
+    Sprint
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        GameMode::Classic
+    }
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "classique",
+            GameMode::Replay => "replay",
+            GameMode::Scan => "scan",
+            GameMode::Sprint => "sprint"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    Shipped,
+    Incidents,
+    /// le pc du joueur n'a plus d'intégrité
+    Crashed
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use crate::component::{player::PlayerInput, weapon::WEAPONS};
+use crate::resources::{GameMode, config::GameConfig, sprint::{Sprint, SprintCommit}};
+
+const INPUT_LEFT: u16 = 1;
+const INPUT_RIGHT: u16 = 1 << 1;
+const INPUT_FIRE: u16 = 1 << 2;
+// bits 3 à 5 : arme choisie (indice + 1), 0 sans changement
+const INPUT_WEAPON_SHIFT: u16 = 3;
+const INPUT_WEAPON_MASK: u16 = 0b111;
+const INPUT_HOLD: u16 = 1 << 6;
+const INPUT_UP: u16 = 1 << 7;
+const INPUT_DOWN: u16 = 1 << 8;
+const INPUT_DASH: u16 = 1 << 9;
+// en coop les commandes du second joueur occupent les deux octets suivants
+const PLAYER_INPUT_BITS: usize = 16;
+
+/// Commandes du joueur pour un pas de simulation, sur deux octets
+pub fn encode_input(input: &PlayerInput) -> u16 {
+    let mut flags = 0;
+    if input.x < 0. {
+        flags |= INPUT_LEFT;
+    }
+    if input.x > 0. {
+        flags |= INPUT_RIGHT;
+    }
+    if input.fire {
+        flags |= INPUT_FIRE;
+    }
+    if input.hold {
+        flags |= INPUT_HOLD;
+    }
+    if input.y > 0. {
+        flags |= INPUT_UP;
+    }
+    if input.y < 0. {
+        flags |= INPUT_DOWN;
+    }
+    if input.dash {
+        flags |= INPUT_DASH;
+    }
+    if let Some(weapon) = input.weapon {
+        flags |= (weapon.index() as u16 + 1) << INPUT_WEAPON_SHIFT;
+    }
+    flags
+}
+
+pub fn decode_input(flags: u16, input: &mut PlayerInput) {
+    input.x = match (flags & INPUT_LEFT != 0, flags & INPUT_RIGHT != 0) {
+        (true, false) => -1.,
+        (false, true) => 1.,
+        _ => 0.
+    };
+    input.fire = flags & INPUT_FIRE != 0;
+    input.hold = flags & INPUT_HOLD != 0;
+    input.y = match (flags & INPUT_DOWN != 0, flags & INPUT_UP != 0) {
+        (true, false) => -1.,
+        (false, true) => 1.,
+        _ => 0.
+    };
+    input.dash = flags & INPUT_DASH != 0;
+    input.weapon = match (flags >> INPUT_WEAPON_SHIFT) & INPUT_WEAPON_MASK {
+        0 => None,
+        index => WEAPONS.get(index as usize - 1).copied()
+    };
+}
+
+/// Commandes du joueur `index` placées dans les commandes de tous les joueurs pour un pas
+pub fn pack_input(index: usize, flags: u16) -> u32 {
+    (flags as u32) << (PLAYER_INPUT_BITS * index)
+}
+
+pub fn unpack_input(index: usize, packed: u32) -> u16 {
+    (packed >> (PLAYER_INPUT_BITS * index)) as u16
+}
+
+fn single_player() -> usize {
+    1
+}
+
+/// Partie enregistrée : la graine, les réglages du début de manche
+/// et les commandes de chaque pas, compressées par plages (valeur, répétitions)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub score: u32,
+    pub config: GameConfig,
+    /// absent des replays d'avant la coop
+    #[serde(default = "single_player")]
+    pub players: usize,
+    /// mode de la partie enregistrée, et les commits joués en sprint :
+    /// les vagues du sprint remplacent le tirage des ennemis
+    #[serde(default)]
+    pub mode: GameMode,
+    #[serde(default)]
+    pub sprint_commits: Vec<SprintCommit>,
+    pub inputs: Vec<(u32, u32)>
+}
+
+impl Replay {
+    pub fn new(seed: u64, score: u32, config: GameConfig, players: usize, inputs: &[u32]) -> Self {
+        let mut runs: Vec<(u32, u32)> = vec![];
+        for &flags in inputs {
+            match runs.last_mut() {
+                Some((last, count)) if *last == flags => *count += 1,
+                _ => runs.push((flags, 1))
+            }
+        }
+
+        Replay { seed, score, config, players, mode: GameMode::Classic, sprint_commits: vec![], inputs: runs }
+    }
+
+    pub fn with_mode(mut self, mode: GameMode, sprint_commits: &[SprintCommit]) -> Self {
+        self.mode = mode;
+        self.sprint_commits = if mode == GameMode::Sprint { sprint_commits.to_vec() } else { vec![] };
+        self
+    }
+
+    /// Sprint à remettre en place pour la lecture, `None` pour une partie hors sprint
+    pub fn sprint(&self) -> Option<Sprint> {
+        if self.mode == GameMode::Sprint {
+            Some(Sprint::new(self.sprint_commits.clone()))
+        } else {
+            None
+        }
+    }
+
+    pub fn ticks(&self) -> Vec<u32> {
+        self.inputs
+            .iter()
+            .flat_map(|&(flags, count)| std::iter::repeat(flags).take(count as usize))
+            .collect()
+    }
+}
+
+/// Commandes de la manche en cours, deux octets par joueur et par pas de simulation
+#[derive(Default)]
+pub struct ReplayRecorder {
+    pub config: GameConfig,
+    pub players: usize,
+    pub mode: GameMode,
+    pub sprint_commits: Vec<SprintCommit>,
+    pub inputs: Vec<u32>
+}
+
+/// Lecture en cours, `None` en partie normale
+#[derive(Default)]
+pub struct ReplayPlayback {
+    pub current: Option<Playback>
+}
+
+pub struct Playback {
+    pub replay: Replay,
+    pub ticks: Vec<u32>,
+    pub cursor: usize,
+    /// réglages, mode, nombre de joueurs et commits du sprint de l'utilisateur,
+    /// remis en place à la fin du replay
+    pub saved_config: GameConfig,
+    pub saved_mode: GameMode,
+    pub saved_players: usize,
+    pub saved_sprint: Option<Vec<SprintCommit>>
+}
+
+impl Playback {
+    pub fn new(
+        replay: Replay,
+        saved_config: GameConfig,
+        saved_mode: GameMode,
+        saved_players: usize,
+        saved_sprint: Option<Vec<SprintCommit>>
+    ) -> Self {
+        let ticks = replay.ticks();
+        Playback { replay, ticks, cursor: 0, saved_config, saved_mode, saved_players, saved_sprint }
+    }
+
+    /// Commandes du pas suivant, neutres une fois l'enregistrement épuisé
+    pub fn next(&mut self) -> u32 {
+        let flags = self.ticks.get(self.cursor).copied().unwrap_or(0);
+        self.cursor += 1;
+        flags
+    }
+}
+
+/// Replays proposés à l'écran de sélection, (nom du fichier, contenu), et ligne sélectionnée
+#[derive(Default)]
+pub struct ReplayBrowser {
+    pub entries: Vec<(String, Replay)>,
+    pub selected: usize
+}
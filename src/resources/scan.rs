@@ -0,0 +1,52 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Commentaire TODO/FIXME/XXX/HACK trouvé dans le dossier scanné
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceComment {
+    /// chemin relatif au dossier scanné
+    pub path: String,
+    pub line: u32,
+    pub tag: String,
+    pub text: String
+}
+
+impl SourceComment {
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.path, self.line)
+    }
+}
+
+/// Commentaires du dossier passé avec `--scan`, portés par les ennemis de la manche
+pub struct ScanComments {
+    pub all: Vec<SourceComment>,
+    /// indices dans `all` pas encore portés par un ennemi cette manche
+    pub available: Vec<usize>,
+    /// indices dans `all` des commentaires dont l'ennemi a été détruit, dans l'ordre
+    pub fixed: Vec<usize>,
+    // tirage séparé de GameRng : une manche (ou un replay) se joue pareil avec ou sans scan
+    rng: Option<StdRng>
+}
+
+impl ScanComments {
+    pub fn new(all: Vec<SourceComment>) -> Self {
+        let available = (0..all.len()).collect();
+        ScanComments { all, available, fixed: vec![], rng: None }
+    }
+
+    pub fn restart(&mut self) {
+        self.available = (0..self.all.len()).collect();
+        self.fixed.clear();
+        self.rng = None;
+    }
+
+    /// Tire un commentaire pas encore en jeu, `None` quand ils ont tous été distribués.
+    /// Le tirage est semé avec la graine de la manche au premier appel
+    pub fn take(&mut self, seed: u64) -> Option<usize> {
+        if self.available.is_empty() {
+            return None;
+        }
+        let rng = self.rng.get_or_insert_with(|| StdRng::seed_from_u64(seed));
+        let position = rng.gen_range(0..self.available.len());
+        Some(self.available.swap_remove(position))
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Entity;
+use serde::{Deserialize, Serialize};
+
+use crate::component::enemy::EnemyKind;
+
+// une vague ne dépasse pas ce nombre d'ennemis, meme pour un gros commit
+const MAX_WAVE_ENEMIES: u32 = 8;
+// mots entiers d'un sujet de commit qui annoncent un correctif ou un revert
+const TOUGH_WORDS: [&str; 6] = ["fix", "fixes", "fixed", "revert", "reverts", "reverted"];
+
+/// Commit du dépot lu avec `--sprint`, joué comme une vague d'ennemis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SprintCommit {
+    pub hash: String,
+    pub author: String,
+    pub subject: String,
+    pub files_changed: u32
+}
+
+impl SprintCommit {
+    pub fn enemy_count(&self) -> u32 {
+        self.files_changed.clamp(1, MAX_WAVE_ENEMIES)
+    }
+
+    /// Un correctif ou un revert cache souvent un bug coriace
+    pub fn is_tough(&self) -> bool {
+        self.subject
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| TOUGH_WORDS.contains(&word))
+    }
+
+    pub fn enemy_kind(&self) -> EnemyKind {
+        if self.is_tough() {
+            EnemyKind::LegacyCode
+        } else {
+            EnemyKind::Fixme
+        }
+    }
+}
+
+/// Avancement du sprint : un commit est livré quand tous ses ennemis sont détruits
+pub struct Sprint {
+    /// du plus ancien au plus récent
+    pub commits: Vec<SprintCommit>,
+    pub wave: usize,
+    pub spawned_in_wave: u32,
+    pub destroyed: Vec<u32>,
+    pub escaped: Vec<u32>,
+    /// ennemis en jeu et l'indice de leur commit
+    pub alive: HashMap<Entity, usize>
+}
+
+impl Sprint {
+    pub fn new(commits: Vec<SprintCommit>) -> Self {
+        let len = commits.len();
+        Sprint {
+            commits,
+            wave: 0,
+            spawned_in_wave: 0,
+            destroyed: vec![0; len],
+            escaped: vec![0; len],
+            alive: HashMap::new()
+        }
+    }
+
+    pub fn restart(&mut self) {
+        *self = Sprint::new(std::mem::take(&mut self.commits));
+    }
+
+    /// Commit du prochain ennemi à faire apparaitre, `None` une fois toutes les vagues lancées
+    pub fn next_enemy(&mut self) -> Option<usize> {
+        let commit = self.commits.get(self.wave)?;
+        let index = self.wave;
+
+        self.spawned_in_wave += 1;
+        if self.spawned_in_wave >= commit.enemy_count() {
+            self.wave += 1;
+            self.spawned_in_wave = 0;
+        }
+        Some(index)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.wave >= self.commits.len() && self.alive.is_empty()
+    }
+
+    pub fn is_shipped(&self, index: usize) -> bool {
+        self.destroyed[index] == self.commits[index].enemy_count()
+    }
+}
@@ -2,5 +2,9 @@
 pub enum AppState {
     MainMenu,
     InGame,
-    EndGame
+    Paused,
+    EndGame,
+    HighScores,
+    Replay,
+    Controls
 }
\ No newline at end of file
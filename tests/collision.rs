@@ -0,0 +1,84 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::{enemy::{Enemy, EnemyKind}, weapon::WeaponKind};
+use foyer_rust_mep_horror::events::ShotFired;
+use foyer_rust_mep_horror::resources::Scoring;
+
+#[test]
+fn laser_destroys_enemy_and_scores() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let enemy = game.spawn_enemy(EnemyKind::Fixme, Vec3::new(0., 100., 10.));
+    let laser = game.spawn_laser(Vec3::new(0., 90., 10.));
+
+    game.tick(1);
+
+    assert!(!game.exists(enemy));
+    assert!(!game.exists(laser));
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.bug_fix, 1);
+    assert_eq!(scoring.hits, 1);
+    assert_eq!(scoring.points, EnemyKind::Fixme.score());
+}
+
+#[test]
+fn tough_enemy_survives_a_single_hit() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let enemy = game.spawn_enemy(EnemyKind::LegacyCode, Vec3::new(0., 100., 10.));
+    let laser = game.spawn_laser(Vec3::new(0., 90., 10.));
+
+    game.tick(1);
+
+    assert!(game.exists(enemy));
+    assert!(!game.exists(laser));
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.hits, 1);
+    assert_eq!(scoring.bug_fix, 0);
+    assert_eq!(scoring.points, 0);
+}
+
+#[test]
+fn laser_away_from_enemy_misses() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.spawn_enemy(EnemyKind::Fixme, Vec3::new(-200., 100., 10.));
+    game.spawn_laser(Vec3::new(200., 90., 10.));
+
+    game.tick(10);
+
+    assert_eq!(game.count::<Enemy>(), 1);
+    assert_eq!(game.resource::<Scoring>().hits, 0);
+}
+
+#[test]
+fn hotfix_pierces_through_enemies() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let first = game.spawn_enemy(EnemyKind::Fixme, Vec3::new(0., 100., 10.));
+    let second = game.spawn_enemy(EnemyKind::Fixme, Vec3::new(0., 104., 10.));
+    let laser = game.spawn_projectile(WeaponKind::Hotfix, Vec3::new(0., 90., 10.));
+    // le fichier est posé par le test, le tir est compté à la main
+    game.app.world.resource_mut::<Events<ShotFired>>().send(ShotFired { player: 0 });
+
+    game.tick(1);
+
+    assert!(!game.exists(first));
+    assert!(!game.exists(second));
+    assert!(game.exists(laser));
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.bug_fix, 2);
+    assert_eq!(scoring.hits, 1);
+    assert!(scoring.accuracy() <= 100.);
+}
+
+#[test]
+fn hotfix_hits_each_enemy_once() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let enemy = game.spawn_enemy(EnemyKind::LegacyCode, Vec3::new(0., 100., 10.));
+    game.spawn_projectile(WeaponKind::Hotfix, Vec3::new(0., 90., 10.));
+
+    game.tick(3);
+
+    assert!(game.exists(enemy));
+    assert_eq!(game.resource::<Scoring>().hits, 1);
+}
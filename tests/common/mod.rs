@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use bevy::ecs::system::CommandQueue;
+use bevy::input::{ButtonState, gamepad::GamepadEventRaw, keyboard::KeyboardInput};
+
+use foyer_rust_mep_horror::build_game_app;
+use foyer_rust_mep_horror::component::enemy::EnemyKind;
+use foyer_rust_mep_horror::options::LaunchOptions;
+use foyer_rust_mep_horror::component::{player::Player, push_file::PowerUpKind, weapon::WeaponKind};
+use foyer_rust_mep_horror::plugins::{enemy::spawn_enemy_kind, player::spawn_laser, power_up_plugin::spawn_push_file};
+use foyer_rust_mep_horror::resources::{GameFonts, GameTextures};
+use foyer_rust_mep_horror::resources::config::{DifficultyStep, GameConfig};
+use foyer_rust_mep_horror::resources::replay::Replay;
+use foyer_rust_mep_horror::resources::sprint::{Sprint, SprintCommit};
+use foyer_rust_mep_horror::states::AppState;
+
+const SEED: u64 = 42;
+
+/// Manche simulée sans fenetre, avancée pas à pas par les tests
+pub struct TestGame {
+    pub app: App
+}
+
+impl TestGame {
+    /// Démarre une manche avec `config` ; le premier update joue deja un pas
+    pub fn new(config: GameConfig) -> Self {
+        Self::start(LaunchOptions::default(), config)
+    }
+
+    /// Meme chose avec deux joueurs
+    pub fn coop(config: GameConfig) -> Self {
+        Self::start(LaunchOptions { coop: true, ..Default::default() }, config)
+    }
+
+    /// Manche de sprint sur `commits`, comme lancée avec `--sprint`
+    pub fn sprint(config: GameConfig, commits: Vec<SprintCommit>) -> Self {
+        Self::start_with(LaunchOptions::default(), config, Some(Sprint::new(commits)))
+    }
+
+    /// Manche rejouée depuis `replay`, lancée sans sprint comme le jeu depuis le menu :
+    /// graine, réglages, joueurs et sprint sont ceux de l'enregistrement
+    pub fn replay(replay: &Replay) -> Self {
+        let options = LaunchOptions {
+            seed: Some(replay.seed),
+            coop: replay.players > 1,
+            ..Default::default()
+        };
+        Self::start_with(options, replay.config.clone(), replay.sprint())
+    }
+
+    fn start(options: LaunchOptions, config: GameConfig) -> Self {
+        Self::start_with(options, config, None)
+    }
+
+    fn start_with(options: LaunchOptions, config: GameConfig, sprint: Option<Sprint>) -> Self {
+        let mut app = build_game_app(LaunchOptions {
+            seed: options.seed.or(Some(SEED)),
+            headless: true,
+            ..options
+        });
+        app.insert_resource(config);
+        if let Some(sprint) = sprint {
+            app.insert_resource(sprint);
+        }
+        app.update();
+
+        TestGame { app }
+    }
+
+    /// Réglages sans apparition d'ennemis ni déplacement des ennemis,
+    /// pour que seules les entités créées par le test soient en jeu
+    pub fn quiet_config() -> GameConfig {
+        GameConfig {
+            enemy_velocity: 0.,
+            difficulty: vec![DifficultyStep { spawn_interval: 1000., ..Default::default() }],
+            power_up_drop_interval: 0.,
+            ..Default::default()
+        }
+    }
+
+    pub fn tick(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    pub fn state(&self) -> AppState {
+        self.app.world.resource::<State<AppState>>().current().clone()
+    }
+
+    pub fn resource<R: Send + Sync + 'static>(&self) -> &R {
+        self.app.world.resource::<R>()
+    }
+
+    pub fn count<C: Component>(&mut self) -> usize {
+        self.app.world
+            .query_filtered::<Entity, With<C>>()
+            .iter(&self.app.world)
+            .count()
+    }
+
+    pub fn exists(&self, entity: Entity) -> bool {
+        self.app.world.get_entity(entity).is_some()
+    }
+
+    /// Appui clavier, vu par les systèmes au prochain `tick`
+    pub fn press(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Pressed);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.send_key(key, ButtonState::Released);
+    }
+
+    fn send_key(&mut self, key: KeyCode, state: ButtonState) {
+        self.app.world.resource_mut::<Events<KeyboardInput>>().send(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key),
+            state
+        });
+    }
+
+    /// Manette simulée, comme si gilrs l'avait détectée
+    pub fn connect_gamepad(&mut self, id: usize) {
+        self.send_gamepad(id, GamepadEventType::Connected);
+    }
+
+    pub fn disconnect_gamepad(&mut self, id: usize) {
+        self.send_gamepad(id, GamepadEventType::Disconnected);
+    }
+
+    pub fn press_button(&mut self, id: usize, button: GamepadButtonType) {
+        self.send_gamepad(id, GamepadEventType::ButtonChanged(button, 1.));
+    }
+
+    pub fn release_button(&mut self, id: usize, button: GamepadButtonType) {
+        self.send_gamepad(id, GamepadEventType::ButtonChanged(button, 0.));
+    }
+
+    pub fn move_stick(&mut self, id: usize, x: f32, y: f32) {
+        self.send_gamepad(id, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, x));
+        self.send_gamepad(id, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, y));
+    }
+
+    fn send_gamepad(&mut self, id: usize, event_type: GamepadEventType) {
+        self.app.world.resource_mut::<Events<GamepadEventRaw>>().send(GamepadEventRaw(Gamepad(id), event_type));
+    }
+
+    pub fn spawn_enemy(&mut self, kind: EnemyKind, translation: Vec3) -> Entity {
+        self.with_commands(|commands, game_textures, _, config| {
+            spawn_enemy_kind(commands, game_textures, config, kind, translation, 1.)
+        })
+    }
+
+    pub fn spawn_laser(&mut self, translation: Vec3) -> Entity {
+        self.spawn_projectile(WeaponKind::Scala, translation)
+    }
+
+    pub fn spawn_projectile(&mut self, kind: WeaponKind, translation: Vec3) -> Entity {
+        self.with_commands(|commands, game_textures, _, config| {
+            spawn_laser(commands, game_textures, config, kind, translation, 0.)
+        })
+    }
+
+    /// Fichier bonus posé sur le pc du joueur, ramassé au prochain `tick`
+    pub fn spawn_power_up(&mut self, power_up: PowerUpKind) -> Entity {
+        let translation = self.player_translation();
+        self.with_commands(|commands, game_textures, game_fonts, config| {
+            spawn_push_file(commands, game_textures, game_fonts, config, power_up, translation)
+        })
+    }
+
+    pub fn player_translation(&mut self) -> Vec3 {
+        self.player_translation_of(0)
+    }
+
+    pub fn player_translation_of(&mut self, index: usize) -> Vec3 {
+        self.app.world
+            .query::<(&Player, &Transform)>()
+            .iter(&self.app.world)
+            .find(|(player, _)| player.index == index)
+            .map(|(_, transform)| transform.translation)
+            .expect("pas de pc pour ce joueur")
+    }
+
+    fn with_commands<T>(&mut self, f: impl FnOnce(&mut Commands, &GameTextures, &GameFonts, &GameConfig) -> T) -> T {
+        let mut queue = CommandQueue::default();
+        let result = {
+            let world = &self.app.world;
+            let mut commands = Commands::new(&mut queue, world);
+            f(&mut commands, world.resource::<GameTextures>(), world.resource::<GameFonts>(), world.resource::<GameConfig>())
+        };
+        queue.apply(&mut self.app.world);
+        result
+    }
+}
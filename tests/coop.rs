@@ -0,0 +1,113 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::{enemy::EnemyKind, laser::Laser, player::FromPlayer};
+use foyer_rust_mep_horror::resources::{EndReason, Scoring, config::GameConfig};
+use foyer_rust_mep_horror::factory::replay_factory::load_replay;
+use foyer_rust_mep_horror::resources::keymap::{Action, KeyMap};
+use foyer_rust_mep_horror::resources::replay::{Replay, pack_input, unpack_input};
+use foyer_rust_mep_horror::states::AppState;
+
+#[test]
+fn second_player_keys_move_only_the_second_pc() {
+    let mut game = TestGame::coop(TestGame::quiet_config());
+    let first = game.player_translation_of(0);
+    let second = game.player_translation_of(1);
+
+    game.press(KeyCode::D);
+    game.tick(10);
+
+    assert_eq!(game.player_translation_of(0), first);
+    assert!(game.player_translation_of(1).x > second.x);
+}
+
+#[test]
+fn second_player_moves_with_a_rebound_key() {
+    let mut game = TestGame::coop(TestGame::quiet_config());
+    game.app.world.resource_mut::<KeyMap>().bind_player(1, Action::MoveRight, KeyCode::L);
+    let second = game.player_translation_of(1);
+
+    game.press(KeyCode::D);
+    game.tick(10);
+    game.release(KeyCode::D);
+    assert_eq!(game.player_translation_of(1), second);
+
+    game.press(KeyCode::L);
+    game.tick(10);
+    assert!(game.player_translation_of(1).x > second.x);
+}
+
+#[test]
+fn kills_are_scored_for_the_shooter() {
+    let mut game = TestGame::coop(TestGame::quiet_config());
+    let target = game.player_translation_of(1) + Vec3::new(0., 60., 0.);
+    let enemy = game.spawn_enemy(EnemyKind::Fixme, target);
+
+    game.press(KeyCode::A);
+    game.tick(1);
+    game.release(KeyCode::A);
+    game.tick(1);
+    let owners = game.app.world
+        .query_filtered::<&FromPlayer, With<Laser>>()
+        .iter(&game.app.world)
+        .map(|from| from.0)
+        .collect::<Vec<usize>>();
+    assert_eq!(owners, vec![1]);
+
+    game.tick(30);
+
+    assert!(!game.exists(enemy));
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.bug_fix, 1);
+    assert_eq!(scoring.players[1].bug_fix, 1);
+    assert_eq!(scoring.players[1].shots_fired, 1);
+    assert_eq!(scoring.players[0].bug_fix, 0);
+    assert_eq!(scoring.players[0].shots_fired, 0);
+}
+
+#[test]
+fn round_goes_on_until_both_pcs_crash() {
+    let mut game = TestGame::coop(GameConfig {
+        player_integrity: 1,
+        ..TestGame::quiet_config()
+    });
+    // juste au-dessus du pc, sinon l'ennemi passe en prod avant le choc
+    let above = Vec3::new(0., 10., 0.);
+
+    let first = game.player_translation_of(0);
+    game.spawn_enemy(EnemyKind::Fixme, first + above);
+    game.tick(3);
+    assert_eq!(game.state(), AppState::InGame);
+
+    let second = game.player_translation_of(1);
+    game.spawn_enemy(EnemyKind::Fixme, second + above);
+    game.tick(3);
+    assert_eq!(game.state(), AppState::EndGame);
+    assert_eq!(*game.resource::<EndReason>(), EndReason::Crashed);
+}
+
+#[test]
+fn inputs_of_both_players_share_a_tick() {
+    let packed = pack_input(0, 0b101) | pack_input(1, 0b1000_0010);
+
+    assert_eq!(unpack_input(0, packed), 0b101);
+    assert_eq!(unpack_input(1, packed), 0b1000_0010);
+}
+
+#[test]
+fn replays_with_an_invalid_player_count_are_rejected() {
+    let dir = std::env::temp_dir().join(format!("foyer-replays-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for (players, valid) in [(0, false), (1, true), (2, true), (3, false)] {
+        let path = dir.join(format!("{}-joueurs.ron", players));
+        let replay = Replay::new(42, 0, GameConfig::default(), players, &[0, 0, 1]);
+        std::fs::write(&path, ron::to_string(&replay).unwrap()).unwrap();
+
+        assert_eq!(load_replay(&path).is_ok(), valid, "{} joueurs", players);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::enemy::Enemy;
+use foyer_rust_mep_horror::resources::config::{DifficultyStep, GameConfig};
+
+fn fast_spawn(enemy_max: u32, enemy_max_factor: f32) -> GameConfig {
+    GameConfig {
+        enemy_max,
+        enemy_velocity: 0.,
+        difficulty: vec![DifficultyStep { enemy_max_factor, spawn_interval: 0., ..Default::default() }],
+        power_up_drop_interval: 0.,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn spawns_stop_at_enemy_max() {
+    let mut game = TestGame::new(fast_spawn(3, 1.));
+    game.tick(120);
+
+    assert_eq!(game.count::<Enemy>(), 3);
+}
+
+#[test]
+fn difficulty_raises_enemy_cap() {
+    let mut game = TestGame::new(fast_spawn(3, 2.));
+    game.tick(120);
+
+    assert_eq!(game.count::<Enemy>(), 6);
+}
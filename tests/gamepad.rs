@@ -0,0 +1,99 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::laser::Laser;
+use foyer_rust_mep_horror::resources::gamepad::{Controllers, InputDevice, prompt_text};
+use foyer_rust_mep_horror::resources::keymap::{Action, KeyMap};
+use foyer_rust_mep_horror::states::AppState;
+
+#[test]
+fn stick_moves_the_player_outside_the_dead_zone() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.connect_gamepad(0);
+    let start = game.player_translation();
+
+    game.move_stick(0, 0.2, 0.1);
+    game.tick(30);
+    assert_eq!(game.player_translation(), start);
+
+    game.move_stick(0, 0.8, 0.1);
+    game.tick(30);
+    let moved = game.player_translation();
+    assert!(moved.x > start.x);
+    assert_eq!(moved.y, start.y);
+}
+
+#[test]
+fn dpad_and_south_button_drive_the_player() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.connect_gamepad(0);
+    let start = game.player_translation();
+
+    game.press_button(0, GamepadButtonType::DPadLeft);
+    game.tick(10);
+    game.release_button(0, GamepadButtonType::DPadLeft);
+    assert!(game.player_translation().x < start.x);
+
+    game.press_button(0, GamepadButtonType::South);
+    game.tick(1);
+    game.release_button(0, GamepadButtonType::South);
+    game.tick(1);
+    assert_eq!(game.count::<Laser>(), 1);
+}
+
+#[test]
+fn unplugging_the_gamepad_pauses_the_round() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.connect_gamepad(0);
+    game.tick(1);
+    assert_eq!(game.resource::<Controllers>().gamepad, Some(Gamepad(0)));
+
+    game.disconnect_gamepad(0);
+    game.tick(2);
+    assert_eq!(game.resource::<Controllers>().gamepad, None);
+    assert_eq!(game.state(), AppState::Paused);
+}
+
+#[test]
+fn prompts_follow_the_last_used_device() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.connect_gamepad(0);
+    game.press_button(0, GamepadButtonType::South);
+    game.tick(1);
+    assert_eq!(game.resource::<Controllers>().device, InputDevice::Gamepad);
+
+    game.press(KeyCode::Left);
+    game.tick(1);
+    assert_eq!(game.resource::<Controllers>().device, InputDevice::Keyboard);
+
+    let key_map = KeyMap::default();
+    assert_eq!(prompt_text("Press {} pour jouer", &[Action::Confirm], &key_map, InputDevice::Keyboard), "Press [Space] pour jouer");
+    assert_eq!(prompt_text("Press {} pour jouer", &[Action::Confirm], &key_map, InputDevice::Gamepad), "Press (A) pour jouer");
+}
+
+#[test]
+fn pause_commands_have_gamepad_buttons() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.connect_gamepad(0);
+    game.tick(1);
+
+    game.press_button(0, GamepadButtonType::Start);
+    game.tick(2);
+    game.release_button(0, GamepadButtonType::Start);
+    assert_eq!(game.state(), AppState::Paused);
+
+    game.press_button(0, GamepadButtonType::Select);
+    game.tick(2);
+    game.release_button(0, GamepadButtonType::Select);
+    assert_eq!(game.state(), AppState::MainMenu);
+
+    let key_map = KeyMap::default();
+    assert_eq!(prompt_text("Press {} pour changer", &[Action::TogglePlayers], &key_map, InputDevice::Keyboard), "Press [2] pour changer");
+    assert_eq!(prompt_text("Press {} pour changer", &[Action::TogglePlayers], &key_map, InputDevice::Gamepad), "Press (LB) pour changer");
+    assert_eq!(
+        prompt_text("{} scores, {} replays, {} commandes", &[Action::HighScores, Action::Replays, Action::Controls], &key_map, InputDevice::Gamepad),
+        "(Y) scores, (X) replays, (Select) commandes"
+    );
+}
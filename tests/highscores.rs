@@ -0,0 +1,24 @@
+use foyer_rust_mep_horror::resources::highscores::{HighScoreEntry, HighScores, HIGH_SCORES_MAX};
+
+fn entry(score: u32) -> HighScoreEntry {
+    HighScoreEntry {
+        name: format!("joueur {}", score),
+        score,
+        date: "2022-08-01".to_string(),
+        mode: "classique".to_string(),
+        replay: Some(format!("{}.ron", score))
+    }
+}
+
+#[test]
+fn scores_pushed_out_of_the_top_are_returned() {
+    let mut high_scores = HighScores::default();
+    for score in 1..=HIGH_SCORES_MAX as u32 {
+        assert!(high_scores.insert(entry(score * 10)).is_empty());
+    }
+
+    let dropped = high_scores.insert(entry(55));
+
+    assert_eq!(high_scores.entries.len(), HIGH_SCORES_MAX);
+    assert_eq!(dropped, vec![entry(10)]);
+}
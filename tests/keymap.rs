@@ -0,0 +1,102 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::laser::Laser;
+use foyer_rust_mep_horror::resources::keymap::{Action, KeyMap, is_bindable};
+
+#[test]
+fn names_round_trip() {
+    let mut key_map = KeyMap::default();
+    key_map.bind(Action::Fire, KeyCode::Space);
+    key_map.bind(Action::Dash, KeyCode::F1);
+
+    let (loaded, ignored) = KeyMap::from_names(&key_map.names());
+
+    assert_eq!(loaded, key_map);
+    assert!(ignored.is_empty());
+}
+
+#[test]
+fn binding_a_used_key_swaps_the_actions() {
+    let mut key_map = KeyMap::default();
+    let confirm = key_map.key(Action::Confirm);
+    let fire = key_map.key(Action::Fire);
+
+    key_map.bind(Action::Fire, confirm);
+
+    assert_eq!(key_map.key(Action::Fire), confirm);
+    assert_eq!(key_map.key(Action::Confirm), fire);
+}
+
+#[test]
+fn unknown_or_duplicate_names_keep_the_default_key() {
+    let names = BTreeMap::from([
+        (Action::Fire, "Souris".to_string()),
+        (Action::MoveLeft, "Q".to_string()),
+        (Action::MoveRight, "Q".to_string()),
+        (Action::Dash, "Tab".to_string())
+    ]);
+
+    let (key_map, ignored) = KeyMap::from_names(&names);
+
+    assert_eq!(key_map.key(Action::Fire), KeyCode::X);
+    assert_eq!(key_map.key(Action::MoveLeft), KeyCode::Left);
+    assert_eq!(key_map.key(Action::MoveRight), KeyCode::Right);
+    assert_eq!(key_map.key(Action::Dash), KeyCode::Tab);
+    assert_eq!(ignored.len(), 3);
+}
+
+#[test]
+fn fixed_keys_cannot_be_bound() {
+    for key in [KeyCode::H, KeyCode::R, KeyCode::C, KeyCode::M, KeyCode::Key1, KeyCode::Key9, KeyCode::Delete] {
+        assert!(!is_bindable(key), "{:?}", key);
+    }
+
+    let names = BTreeMap::from([
+        (Action::Confirm, "H".to_string()),
+        (Action::Quit, "F".to_string())
+    ]);
+    let (key_map, ignored) = KeyMap::from_names(&names);
+
+    assert_eq!(key_map.key(Action::Confirm), KeyCode::Space);
+    assert_eq!(key_map.key(Action::Quit), KeyCode::M);
+    assert_eq!(ignored, vec!["H".to_string(), "F".to_string()]);
+}
+
+#[test]
+fn second_player_keys_are_bound_and_swapped_with_the_first_player() {
+    let mut key_map = KeyMap::default();
+    assert_eq!(key_map.player_key(1, Action::MoveLeft), Some(KeyCode::Q));
+    assert_eq!(key_map.player_key(1, Action::Pause), None);
+
+    // X est le tir du premier joueur, il récupère le A du second
+    key_map.bind_player(1, Action::Fire, KeyCode::X);
+    assert_eq!(key_map.player_key(1, Action::Fire), Some(KeyCode::X));
+    assert_eq!(key_map.key(Action::Fire), KeyCode::A);
+
+    let (loaded, ignored) = KeyMap::from_settings(&key_map.settings());
+    assert_eq!(loaded, key_map);
+    assert!(ignored.is_empty());
+}
+
+#[test]
+fn player_fires_with_the_bound_key() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.app.world.resource_mut::<KeyMap>().bind(Action::Fire, KeyCode::F);
+
+    game.press(KeyCode::X);
+    game.tick(1);
+    game.release(KeyCode::X);
+    game.tick(1);
+    assert_eq!(game.count::<Laser>(), 0);
+
+    game.press(KeyCode::F);
+    game.tick(1);
+    game.release(KeyCode::F);
+    game.tick(1);
+    assert_eq!(game.count::<Laser>(), 1);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::{health::Invulnerability, player::Player, velocity::Velocity};
+use foyer_rust_mep_horror::resources::{WinSize, config::GameConfig};
+
+fn player_velocity(game: &mut TestGame) -> Vec2 {
+    let velocity = game.app.world
+        .query_filtered::<&Velocity, With<Player>>()
+        .single(&game.app.world);
+    Vec2::new(velocity.x, velocity.y)
+}
+
+fn player_invulnerable(game: &mut TestGame) -> bool {
+    game.app.world
+        .query_filtered::<&Invulnerability, With<Player>>()
+        .single(&game.app.world)
+        .is_active()
+}
+
+#[test]
+fn player_stays_inside_the_window() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let half_width = GameConfig::default().sprite_size.0 / 2.;
+    let window_width = game.resource::<WinSize>().w;
+
+    game.press(KeyCode::Left);
+    game.tick(200);
+
+    assert_eq!(game.player_translation().x, -window_width / 2. + half_width);
+    assert_eq!(player_velocity(&mut game).x, 0.);
+}
+
+#[test]
+fn player_only_climbs_into_the_lower_zone() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let start = game.player_translation().y;
+
+    game.press(KeyCode::Up);
+    game.tick(200);
+
+    let top = game.player_translation().y;
+    assert!(top > start);
+    assert!(top < 0.);
+
+    game.release(KeyCode::Up);
+    game.press(KeyCode::Down);
+    game.tick(200);
+    assert_eq!(game.player_translation().y, start);
+}
+
+#[test]
+fn diagonal_speed_is_normalized_after_acceleration() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+
+    game.press(KeyCode::Right);
+    game.press(KeyCode::Up);
+    game.tick(1);
+    assert!(player_velocity(&mut game).length() < 0.5);
+
+    game.tick(10);
+    assert!((player_velocity(&mut game).length() - 1.).abs() < 1e-4);
+}
+
+#[test]
+fn dash_is_fast_and_invulnerable() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+
+    game.press(KeyCode::Right);
+    game.press(KeyCode::LShift);
+    game.tick(1);
+
+    assert!(player_velocity(&mut game).x > 1.);
+    assert!(player_invulnerable(&mut game));
+}
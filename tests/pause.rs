@@ -0,0 +1,59 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::TIME_STEP;
+use foyer_rust_mep_horror::component::{enemy::{Enemy, EnemyKind}, player::Player};
+use foyer_rust_mep_horror::resources::{Lives, Scoring, Timer};
+use foyer_rust_mep_horror::states::AppState;
+
+// manche entamée : du temps écoulé, des points, une vie perdue et un ennemi à l'écran
+fn play_a_little(game: &mut TestGame) {
+    game.spawn_enemy(EnemyKind::Fixme, Vec3::new(0., 100., 10.));
+    game.tick(30);
+    game.app.world.resource_mut::<Scoring>().points = 12;
+    game.app.world.resource_mut::<Lives>().0 -= 1;
+}
+
+fn pause_and_restart(game: &mut TestGame) {
+    game.press(KeyCode::P);
+    game.tick(2);
+    game.release(KeyCode::P);
+    assert_eq!(game.state(), AppState::Paused);
+
+    game.press(KeyCode::R);
+    game.tick(2);
+    game.release(KeyCode::R);
+    assert_eq!(game.state(), AppState::InGame);
+}
+
+fn assert_fresh_round(game: &mut TestGame, players: usize) {
+    let round_time = TestGame::quiet_config().round_time;
+    // au plus un pas joué depuis la relance
+    assert!(round_time - game.resource::<Timer>().0 <= 2. * TIME_STEP);
+    assert_eq!(game.resource::<Scoring>().points, 0);
+    assert_eq!(game.resource::<Lives>().0, TestGame::quiet_config().lives);
+    assert_eq!(game.count::<Enemy>(), 0);
+    assert_eq!(game.count::<Player>(), players);
+}
+
+#[test]
+fn restart_from_pause_starts_a_fresh_round() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    play_a_little(&mut game);
+
+    pause_and_restart(&mut game);
+
+    assert_fresh_round(&mut game, 1);
+}
+
+#[test]
+fn restart_from_pause_respawns_both_pcs_in_coop() {
+    let mut game = TestGame::coop(TestGame::quiet_config());
+    play_a_little(&mut game);
+
+    pause_and_restart(&mut game);
+
+    assert_fresh_round(&mut game, 2);
+}
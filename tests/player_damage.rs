@@ -0,0 +1,67 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::{enemy::{Enemy, EnemyKind}, health::Health, player::Player};
+use foyer_rust_mep_horror::resources::{EndReason, Scoring, config::GameConfig};
+use foyer_rust_mep_horror::states::AppState;
+
+fn player_integrity(game: &mut TestGame) -> u32 {
+    game.app.world
+        .query_filtered::<&Health, With<Player>>()
+        .single(&game.app.world)
+        .current
+}
+
+fn spawn_enemy_on_player(game: &mut TestGame) -> Entity {
+    // juste au-dessus du pc, sinon l'ennemi passe en prod avant le choc
+    let translation = game.player_translation() + Vec3::new(0., 10., 0.);
+    game.spawn_enemy(EnemyKind::Fixme, translation)
+}
+
+#[test]
+fn colliding_enemy_damages_the_player() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let integrity = player_integrity(&mut game);
+    let enemy = spawn_enemy_on_player(&mut game);
+
+    game.tick(1);
+
+    assert!(!game.exists(enemy));
+    assert_eq!(player_integrity(&mut game), integrity - 1);
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.bug_fix, 0);
+    assert_eq!(scoring.escaped, 0);
+}
+
+#[test]
+fn player_is_invulnerable_right_after_a_hit() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let integrity = player_integrity(&mut game);
+    spawn_enemy_on_player(&mut game);
+    game.tick(1);
+
+    let enemy = spawn_enemy_on_player(&mut game);
+    game.tick(1);
+
+    assert!(game.exists(enemy));
+    assert_eq!(player_integrity(&mut game), integrity - 1);
+}
+
+#[test]
+fn player_without_integrity_crashes_the_round() {
+    let mut game = TestGame::new(GameConfig {
+        player_integrity: 1,
+        ..TestGame::quiet_config()
+    });
+    spawn_enemy_on_player(&mut game);
+
+    // le choc a lieu dans le pas simulé du premier tick, qui suit l'Update ;
+    // le passage à l'écran de fin est décidé dans l'Update du tick suivant
+    game.tick(2);
+
+    assert_eq!(game.count::<Enemy>(), 0);
+    assert_eq!(game.state(), AppState::EndGame);
+    assert_eq!(*game.resource::<EndReason>(), EndReason::Crashed);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::TIME_STEP;
+use foyer_rust_mep_horror::component::{enemy::{Enemy, EnemyKind}, push_file::{PushFile, PowerUpKind}};
+use foyer_rust_mep_horror::resources::{Lives, Scoring, Timer};
+
+#[test]
+fn extra_time_is_picked_up_on_contact() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let before = game.resource::<Timer>().0;
+    let push_file = game.spawn_power_up(PowerUpKind::ExtraTime);
+
+    game.tick(1);
+
+    assert!(!game.exists(push_file));
+    let after = game.resource::<Timer>().0;
+    assert!((after - (before - TIME_STEP + 5.)).abs() < 1e-4);
+}
+
+#[test]
+fn hotfix_clears_the_screen_without_new_drops() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.spawn_enemy(EnemyKind::Fixme, Vec3::new(-100., 100., 10.));
+    game.spawn_enemy(EnemyKind::LegacyCode, Vec3::new(100., 100., 10.));
+    game.spawn_power_up(PowerUpKind::Hotfix);
+
+    game.tick(2);
+
+    assert_eq!(game.count::<Enemy>(), 0);
+    assert_eq!(game.count::<PushFile>(), 0);
+    assert_eq!(game.resource::<Scoring>().bug_fix, 2);
+}
+
+#[test]
+fn shield_absorbs_escaped_enemies() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let lives = game.resource::<Lives>().0;
+    game.spawn_power_up(PowerUpKind::Shield);
+    game.tick(1);
+
+    let below = game.player_translation() - Vec3::new(0., 50., 0.);
+    game.spawn_enemy(EnemyKind::Fixme, below);
+    game.tick(1);
+
+    assert_eq!(game.count::<Enemy>(), 0);
+    assert_eq!(game.resource::<Scoring>().escaped, 1);
+    assert_eq!(game.resource::<Lives>().0, lives);
+}
+
+#[test]
+fn score_boost_is_counted_apart_from_the_combo() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.spawn_power_up(PowerUpKind::ScoreBoost);
+    game.tick(1);
+
+    // combo x2, puis doublé par le bonus
+    game.app.world.resource_mut::<Scoring>().combo = 5;
+    game.spawn_enemy(EnemyKind::Fixme, Vec3::new(0., 100., 10.));
+    game.spawn_laser(Vec3::new(0., 90., 10.));
+    game.tick(1);
+
+    let base = EnemyKind::Fixme.score();
+    let scoring = game.resource::<Scoring>();
+    assert_eq!(scoring.base_points, base);
+    assert_eq!(scoring.combo_bonus, base);
+    assert_eq!(scoring.power_up_bonus, 2 * base);
+    assert_eq!(scoring.points, 4 * base);
+}
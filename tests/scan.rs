@@ -0,0 +1,42 @@
+use std::fs;
+
+use foyer_rust_mep_horror::factory::scan_factory::{find_tag, scan_source_tree};
+use foyer_rust_mep_horror::storage::unix_time;
+
+#[test]
+fn finds_tags_as_whole_words() {
+    assert_eq!(find_tag("// TODO: gerer le cas vide"), Some(("TODO", "gerer le cas vide")));
+    assert_eq!(find_tag("x = 1 # FIXME - arrondi faux"), Some(("FIXME", "arrondi faux")));
+    assert_eq!(find_tag("/* HACK contournement */"), Some(("HACK", "contournement")));
+    assert_eq!(find_tag("let todo_list = TODOS;"), None);
+    assert_eq!(find_tag("XXXL"), None);
+}
+
+#[test]
+fn scans_source_files_in_path_order() {
+    let root = std::env::temp_dir().join(format!("mep-horror-scan-{}-{}", std::process::id(), unix_time()));
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("target")).unwrap();
+    fs::write(root.join("src/b.rs"), "fn b() {}\n// XXX: a revoir\n").unwrap();
+    fs::write(root.join("src/a.py"), "# TODO premier\n").unwrap();
+    fs::write(root.join("target/gen.rs"), "// FIXME ignore\n").unwrap();
+    fs::write(root.join("notes.txt"), "TODO pas un source\n").unwrap();
+
+    let comments = scan_source_tree(&root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let found = comments
+        .iter()
+        .map(|comment| (comment.tag.as_str(), comment.location(), comment.text.as_str()))
+        .collect::<Vec<_>>();
+    let separator = std::path::MAIN_SEPARATOR;
+    assert_eq!(found, vec![
+        ("TODO", format!("src{}a.py:1", separator), "premier"),
+        ("XXX", format!("src{}b.rs:2", separator), "a revoir")
+    ]);
+}
+
+#[test]
+fn scan_of_a_missing_directory_fails() {
+    assert!(scan_source_tree(std::path::Path::new("/dossier/qui/n-existe/pas")).is_err());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use std::fs;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::enemy::{Enemy, EnemyKind};
+use foyer_rust_mep_horror::factory::git_factory::{parse_git_log, read_sprint_commits};
+use foyer_rust_mep_horror::options::LaunchOptions;
+use foyer_rust_mep_horror::resources::{GameMode, GameRng, config::{DifficultyStep, GameConfig}};
+use foyer_rust_mep_horror::resources::replay::Replay;
+use foyer_rust_mep_horror::resources::sprint::{Sprint, SprintCommit};
+use foyer_rust_mep_horror::storage::unix_time;
+
+fn commit(subject: &str, files_changed: u32) -> SprintCommit {
+    SprintCommit {
+        hash: "a1b2c3d".to_string(),
+        author: "Alice".to_string(),
+        subject: subject.to_string(),
+        files_changed
+    }
+}
+
+#[test]
+fn parses_commits_with_changed_files() {
+    let output = "\u{1e}a1b2c3d\u{1f}Alice\u{1f}Fix crash au login\n\n 3 files changed, 10 insertions(+)\n\
+                  \u{1e}e4f5a6b\u{1f}Bob\u{1f}Ajoute le menu\n\n 1 file changed, 2 insertions(+)\n\
+                  \u{1e}c7d8e9f\u{1f}Carol\u{1f}Commit vide\n";
+
+    let commits = parse_git_log(output);
+
+    assert_eq!(commits.len(), 3);
+    assert_eq!(commits[0].hash, "a1b2c3d");
+    assert_eq!(commits[0].author, "Alice");
+    assert_eq!(commits[0].files_changed, 3);
+    assert_eq!(commits[0].enemy_kind(), EnemyKind::LegacyCode);
+    assert_eq!(commits[1].files_changed, 1);
+    assert_eq!(commits[1].enemy_kind(), EnemyKind::Fixme);
+    // un commit sans fichier donne quand meme un ennemi
+    assert_eq!(commits[2].files_changed, 0);
+    assert_eq!(commits[2].enemy_count(), 1);
+}
+
+#[test]
+fn only_whole_fix_or_revert_words_are_tough() {
+    for subject in ["fix: crash au login", "Fixes #12", "Revert \"Ajoute le menu\"", "hotfix, fixed"] {
+        assert!(commit(subject, 1).is_tough(), "{}", subject);
+    }
+    for subject in ["Ajoute des fixtures", "fixture pour le login", "fixing-up-docs-later", "prefix des logs"] {
+        assert!(!commit(subject, 1).is_tough(), "{}", subject);
+    }
+}
+
+#[test]
+fn reading_a_directory_outside_git_fails() {
+    let root = std::env::temp_dir().join(format!("mep-horror-sprint-{}-{}", std::process::id(), unix_time()));
+    fs::create_dir_all(&root).unwrap();
+
+    let result = read_sprint_commits(&root, 10);
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn waves_follow_the_commits_until_shipped() {
+    let mut sprint = Sprint::new(vec![commit("Ajoute le menu", 2), commit("Commit vide", 0)]);
+
+    let waves = std::iter::from_fn(|| sprint.next_enemy()).collect::<Vec<usize>>();
+    assert_eq!(waves, vec![0, 0, 1]);
+    assert!(sprint.next_enemy().is_none());
+
+    // un ennemi encore en jeu retient la fin du sprint
+    sprint.alive.insert(Entity::from_raw(1), 1);
+    assert!(!sprint.is_over());
+
+    sprint.destroyed[0] = 2;
+    sprint.escaped[1] = 1;
+    sprint.alive.clear();
+    assert!(sprint.is_shipped(0));
+    assert!(!sprint.is_shipped(1));
+    assert!(sprint.is_over());
+}
+
+#[test]
+fn commits_without_sprint_is_an_error() {
+    let args = ["--commits", "20"].map(String::from);
+    assert!(LaunchOptions::from_args(args.clone()).is_err());
+
+    let args = ["--sprint", ".", "--commits", "20"].map(String::from);
+    assert_eq!(LaunchOptions::from_args(args).unwrap().commits, Some(20));
+}
+
+// type et position de chaque ennemi en jeu, triés pour comparer deux manches
+fn enemies(game: &mut TestGame) -> Vec<(EnemyKind, i32, i32)> {
+    let mut enemies = game.app.world
+        .query_filtered::<(&EnemyKind, &Transform), With<Enemy>>()
+        .iter(&game.app.world)
+        .map(|(kind, transform)| (*kind, transform.translation.x.round() as i32, transform.translation.y.round() as i32))
+        .collect::<Vec<_>>();
+    enemies.sort_by_key(|&(_, x, y)| (x, y));
+    enemies
+}
+
+#[test]
+fn sprint_replay_spawns_the_recorded_waves() {
+    let config = GameConfig {
+        difficulty: vec![DifficultyStep { spawn_interval: 0.2, ..Default::default() }],
+        power_up_drop_interval: 0.,
+        ..Default::default()
+    };
+    let commits = vec![commit("Fix crash au login", 2), commit("Ajoute le menu", 3)];
+
+    let mut recorded = TestGame::sprint(config.clone(), commits.clone());
+    recorded.tick(60);
+    let seed = recorded.resource::<GameRng>().seed;
+    let replay = Replay::new(seed, 0, config, 1, &[]).with_mode(GameMode::Sprint, &commits);
+
+    // le mode et les commits passent par le fichier du replay
+    let replay = ron::from_str::<Replay>(&ron::to_string(&replay).unwrap()).unwrap();
+    assert_eq!(replay.sprint_commits, commits);
+
+    let mut replayed = TestGame::replay(&replay);
+    replayed.tick(60);
+
+    let expected = enemies(&mut recorded);
+    assert!(expected.iter().any(|&(kind, _, _)| kind == EnemyKind::LegacyCode));
+    assert_eq!(enemies(&mut replayed), expected);
+    assert_eq!(replayed.resource::<Sprint>().wave, recorded.resource::<Sprint>().wave);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::TestGame;
+use foyer_rust_mep_horror::TIME_STEP;
+use foyer_rust_mep_horror::resources::{EndReason, Timer};
+use foyer_rust_mep_horror::resources::config::GameConfig;
+use foyer_rust_mep_horror::states::AppState;
+
+fn one_second_round() -> GameConfig {
+    GameConfig {
+        round_time: 1.,
+        ..TestGame::quiet_config()
+    }
+}
+
+#[test]
+fn timer_counts_down_one_step_per_tick() {
+    let mut game = TestGame::new(one_second_round());
+    game.tick(29);
+
+    let remaining = game.resource::<Timer>().0;
+    assert!((remaining - (1. - 30. * TIME_STEP)).abs() < 1e-4);
+    assert_eq!(game.state(), AppState::InGame);
+}
+
+#[test]
+fn round_ends_when_timer_expires() {
+    let mut game = TestGame::new(one_second_round());
+    game.tick(70);
+
+    assert_eq!(game.state(), AppState::EndGame);
+    assert_eq!(*game.resource::<EndReason>(), EndReason::Shipped);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use bevy::prelude::*;
+
+use common::TestGame;
+use foyer_rust_mep_horror::component::{health::Damage, laser::Laser};
+use foyer_rust_mep_horror::component::weapon::{WeaponKind, CHARGE_FULL_TICKS, CHARGE_MIN_TICKS};
+use foyer_rust_mep_horror::resources::{Scoring, config::GameConfig};
+
+fn laser_damages(game: &mut TestGame) -> Vec<u32> {
+    game.app.world
+        .query_filtered::<&Damage, With<Laser>>()
+        .iter(&game.app.world)
+        .map(|damage| damage.0)
+        .collect()
+}
+
+fn tap_fire(game: &mut TestGame) {
+    game.press(KeyCode::X);
+    game.tick(1);
+    game.release(KeyCode::X);
+    game.tick(1);
+}
+
+#[test]
+fn mashing_fire_is_limited_by_cooldown() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+
+    tap_fire(&mut game);
+    tap_fire(&mut game);
+    assert_eq!(game.count::<Laser>(), 1);
+
+    game.tick(WeaponKind::Scala.fire_interval());
+    tap_fire(&mut game);
+    assert_eq!(game.count::<Laser>(), 2);
+}
+
+#[test]
+fn spread_fires_three_files() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+
+    game.press(KeyCode::Key2);
+    game.tick(1);
+    tap_fire(&mut game);
+
+    assert_eq!(game.count::<Laser>(), 3);
+}
+
+#[test]
+fn holding_fire_releases_a_charged_shot() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    let base_damage = GameConfig::default().laser_damage;
+
+    game.press(KeyCode::X);
+    game.tick(CHARGE_FULL_TICKS);
+    game.release(KeyCode::X);
+    game.tick(1);
+
+    let mut damages = laser_damages(&mut game);
+    damages.sort_unstable();
+    // le tir normal à l'appui, puis le tir chargé au relachement
+    assert_eq!(damages, vec![base_damage, base_damage * 3]);
+}
+
+#[test]
+fn charged_shot_waits_for_the_weapon_to_be_ready() {
+    let mut game = TestGame::new(TestGame::quiet_config());
+    game.press(KeyCode::Key4);
+    game.tick(1);
+
+    // le tir normal à l'appui relance le délai, plus long que la charge minimum
+    game.press(KeyCode::X);
+    game.tick(CHARGE_MIN_TICKS);
+    game.release(KeyCode::X);
+    game.tick(1);
+    assert_eq!(game.resource::<Scoring>().shots_fired, 1);
+
+    game.tick(WeaponKind::CodeReview.fire_interval());
+    assert_eq!(game.resource::<Scoring>().shots_fired, 2);
+}